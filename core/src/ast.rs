@@ -2,10 +2,12 @@ use crate::error::{IntErr, Interrupt, Never};
 use crate::eval::evaluate_to_value;
 use crate::ident::Ident;
 use crate::interrupt::test_int;
-use crate::num::{Base, FormattingStyle, Number};
+use crate::num::{Base, FormattingStyle, Measurement, Number};
 use crate::scope::{GetIdentError, Scope};
 use crate::value::{ApplyMulHandling, BuiltInFunction, Value};
+use crate::{Span, SpanKind};
 use std::borrow;
+use std::cmp::Ordering;
 use std::sync::Arc;
 
 #[derive(Clone, Debug)]
@@ -21,6 +23,9 @@ pub(crate) enum Expr<'a> {
     Add(Box<Expr<'a>>, Box<Expr<'a>>),
     ImplicitAdd(Box<Expr<'a>>, Box<Expr<'a>>),
     Sub(Box<Expr<'a>>, Box<Expr<'a>>),
+    // A number with an uncertainty, e.g. `9.8 ± 0.1`
+    PlusMinus(Box<Expr<'a>>, Box<Expr<'a>>),
+    Compose(Box<Expr<'a>>, Box<Expr<'a>>),
     Mul(Box<Expr<'a>>, Box<Expr<'a>>),
     Div(Box<Expr<'a>>, Box<Expr<'a>>),
     Pow(Box<Expr<'a>>, Box<Expr<'a>>),
@@ -35,6 +40,48 @@ pub(crate) enum Expr<'a> {
     Fn(Ident<'a>, Box<Expr<'a>>),
 
     Of(Ident<'a>, Box<Expr<'a>>),
+    // Evaluates to 1 if the comparison holds, or 0 otherwise
+    Compare(Box<Expr<'a>>, Comparison, Box<Expr<'a>>),
+    // Boolean operators, where 0 is false and any other number is true.
+    // The right-hand side is only evaluated if it is needed.
+    And(Box<Expr<'a>>, Box<Expr<'a>>),
+    Or(Box<Expr<'a>>, Box<Expr<'a>>),
+    // Comma-separated list, e.g. function arguments
+    List(Vec<Expr<'a>>),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::Greater => ">",
+            Self::GreaterOrEqual => ">=",
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+        }
+    }
+
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Self::Less => ordering == Ordering::Less,
+            Self::LessOrEqual => ordering != Ordering::Greater,
+            Self::Greater => ordering == Ordering::Greater,
+            Self::GreaterOrEqual => ordering != Ordering::Less,
+            Self::Equal => ordering == Ordering::Equal,
+            Self::NotEqual => ordering != Ordering::Equal,
+        }
+    }
 }
 
 impl<'a> Expr<'a> {
@@ -52,6 +99,8 @@ impl<'a> Expr<'a> {
                 format!("({}+{})", a.format(int)?, b.format(int)?)
             }
             Self::Sub(a, b) => format!("({}-{})", a.format(int)?, b.format(int)?),
+            Self::PlusMinus(a, b) => format!("({}\u{b1}{})", a.format(int)?, b.format(int)?),
+            Self::Compose(a, b) => format!("({} \u{2218} {})", a.format(int)?, b.format(int)?),
             Self::Mul(a, b) => format!("({}*{})", a.format(int)?, b.format(int)?),
             Self::Div(a, b) => format!("({}/{})", a.format(int)?, b.format(int)?),
             Self::Pow(a, b) => format!("({}^{})", a.format(int)?, b.format(int)?),
@@ -68,6 +117,21 @@ impl<'a> Expr<'a> {
                 }
             }
             Self::Of(a, b) => format!("{} of {}", a, b.format(int)?),
+            Self::Compare(a, comparison, b) => format!(
+                "({} {} {})",
+                a.format(int)?,
+                comparison.as_str(),
+                b.format(int)?
+            ),
+            Self::And(a, b) => format!("({} and {})", a.format(int)?, b.format(int)?),
+            Self::Or(a, b) => format!("({} or {})", a.format(int)?, b.format(int)?),
+            Self::List(items) => {
+                let mut formatted_items = vec![];
+                for item in items {
+                    formatted_items.push(item.format(int)?);
+                }
+                format!("({})", formatted_items.join(", "))
+            }
         })
     }
 }
@@ -92,6 +156,7 @@ fn should_compute_inverse(rhs: &Expr<'_>) -> bool {
     false
 }
 
+#[allow(clippy::too_many_lines)]
 pub(crate) fn evaluate<'a, I: Interrupt>(
     expr: Expr<'a>,
     scope: Option<Arc<Scope<'a>>>,
@@ -109,7 +174,10 @@ pub(crate) fn evaluate<'a, I: Interrupt>(
         Expr::<'a>::String(s) => Value::String(s),
         Expr::<'a>::Ident(ident) => resolve_identifier(ident, scope, context, int)?,
         Expr::<'a>::Parens(x) => eval!(*x)?,
-        Expr::<'a>::UnaryMinus(x) => eval!(*x)?.handle_num(|x| Ok(-x), Expr::UnaryMinus, scope)?,
+        Expr::<'a>::UnaryMinus(x) => match eval!(*x)? {
+            Value::Measurement(m) => Value::Measurement(Box::new(-*m)),
+            x => x.handle_num(|x| Ok(-x), Expr::UnaryMinus, scope)?,
+        },
         Expr::<'a>::UnaryPlus(x) => eval!(*x)?.handle_num(Ok, Expr::UnaryPlus, scope)?,
         Expr::<'a>::UnaryDiv(x) => eval!(*x)?.handle_num(
             |x| Number::from(1).div(x, int).map_err(IntErr::into_string),
@@ -125,7 +193,7 @@ pub(crate) fn evaluate<'a, I: Interrupt>(
         Expr::<'a>::Sub(a, b) => {
             let a = eval!(*a)?;
             match a {
-                Value::Num(a) => Value::Num(a.sub(eval!(*b)?.expect_num()?, int)?),
+                a @ (Value::Num(_) | Value::Measurement(_)) => evaluate_sub(a, eval!(*b)?, int)?,
                 f @ Value::BuiltInFunction(_) | f @ Value::Fn(_, _, _) => f.apply(
                     Expr::<'a>::UnaryMinus(b),
                     ApplyMulHandling::OnlyApply,
@@ -136,13 +204,9 @@ pub(crate) fn evaluate<'a, I: Interrupt>(
                 _ => return Err("invalid operands for subtraction".to_string().into()),
             }
         }
-        Expr::<'a>::Mul(a, b) => eval!(*a)?.handle_two_nums(
-            eval!(*b)?,
-            |a, b| a.mul(b, int).map_err(IntErr::into_string),
-            |a| |f| Expr::Mul(f, Box::new(Expr::Num(a))),
-            |a| |f| Expr::Mul(Box::new(Expr::Num(a)), f),
-            scope,
-        )?,
+        Expr::<'a>::PlusMinus(a, b) => evaluate_plus_minus(*a, *b, scope, context, int)?,
+        Expr::<'a>::Compose(a, b) => eval!(*a)?.compose(eval!(*b)?)?,
+        Expr::<'a>::Mul(a, b) => evaluate_mul(eval!(*a)?, eval!(*b)?, scope, int)?,
         Expr::<'a>::Apply(a, b) | Expr::<'a>::ApplyMul(a, b) => {
             if let (Expr::Ident(a), Expr::Ident(b)) = (&*a, &*b) {
                 let ident = format!("{}_{}", a, b);
@@ -152,37 +216,8 @@ pub(crate) fn evaluate<'a, I: Interrupt>(
             }
             eval!(*a)?.apply(*b, ApplyMulHandling::Both, scope, context, int)?
         }
-        Expr::<'a>::Div(a, b) => eval!(*a)?.handle_two_nums(
-            eval!(*b)?,
-            |a, b| a.div(b, int).map_err(IntErr::into_string),
-            |a| |f| Expr::Div(f, Box::new(Expr::Num(a))),
-            |a| |f| Expr::Div(Box::new(Expr::Num(a)), f),
-            scope,
-        )?,
-        Expr::<'a>::Pow(a, b) => {
-            let lhs = eval!(*a)?;
-            if should_compute_inverse(&*b.clone()) {
-                let result = match &lhs {
-                    Value::BuiltInFunction(f) => Some(f.invert()?),
-                    Value::Fn(_, _, _) => {
-                        return Err("inverses of lambda functions are not currently supported"
-                            .to_string()
-                            .into())
-                    }
-                    _ => None,
-                };
-                if let Some(res) = result {
-                    return Ok(res);
-                }
-            }
-            lhs.handle_two_nums(
-                eval!(*b)?,
-                |a, b| a.pow(b, int),
-                |a| |f| Expr::Pow(f, Box::new(Expr::Num(a))),
-                |a| |f| Expr::Pow(Box::new(Expr::Num(a)), f),
-                scope,
-            )?
-        }
+        Expr::<'a>::Div(a, b) => evaluate_div(eval!(*a)?, eval!(*b)?, scope, int)?,
+        Expr::<'a>::Pow(a, b) => evaluate_pow(*a, *b, scope, context, int)?,
         Expr::<'a>::ApplyFunctionCall(a, b) => {
             eval!(*a)?.apply(*b, ApplyMulHandling::OnlyApply, scope, context, int)?
         }
@@ -192,9 +227,239 @@ pub(crate) fn evaluate<'a, I: Interrupt>(
             Ok(value) => value,
             Err(msg) => return Err(msg.to_string().into()),
         },
+        Expr::<'a>::Compare(a, comparison, b) => {
+            evaluate_comparison(*a, comparison, *b, scope, context, int)?
+        }
+        Expr::<'a>::And(a, b) => evaluate_logical(*a, false, *b, scope, context, int)?,
+        Expr::<'a>::Or(a, b) => evaluate_logical(*a, true, *b, scope, context, int)?,
+        Expr::<'a>::List(items) => {
+            let mut values = vec![];
+            for item in items {
+                values.push(eval!(item)?);
+            }
+            Value::List(values)
+        }
     })
 }
 
+// splits a sum like `1 + a - 2` into its terms, returning whether each
+// term is subtracted
+fn sum_terms<'a>(expr: Expr<'a>, negative: bool, terms: &mut Vec<(bool, Expr<'a>)>) {
+    match expr {
+        Expr::Add(a, b) | Expr::ImplicitAdd(a, b) => {
+            sum_terms(*a, negative, terms);
+            sum_terms(*b, negative, terms);
+        }
+        Expr::Sub(a, b) => {
+            sum_terms(*a, negative, terms);
+            sum_terms(*b, !negative, terms);
+        }
+        expr => terms.push((negative, expr)),
+    }
+}
+
+/// Evaluates each term of a sum like `1 + unknown_unit + 2` separately,
+/// adding up the terms that can be evaluated and returning the others as
+/// error spans, e.g. `3 + unknown_unit`. Returns `None` if this doesn't
+/// help, e.g. because no term (or every term) can be evaluated on its own.
+pub(crate) fn evaluate_partially<'a, I: Interrupt>(
+    expr: Expr<'a>,
+    scope: Option<&Arc<Scope<'a>>>,
+    context: &mut crate::Context,
+    int: &I,
+) -> Result<Option<Vec<Span>>, IntErr<String, I>> {
+    let mut terms = vec![];
+    sum_terms(expr, false, &mut terms);
+    let mut total = None;
+    let mut failed_terms = vec![];
+    for (negative, term) in terms {
+        let formatted = match &term {
+            Expr::Ident(ident) => ident.as_str().to_string(),
+            term => term.format(int)?,
+        };
+        let value = match evaluate(term, scope.cloned(), context, int) {
+            Ok(value) => value,
+            Err(IntErr::Error(_)) => {
+                failed_terms.push((negative, formatted));
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let sum = match (total, negative) {
+            (None, false) => Ok(value),
+            (None, true) => value.handle_num(|x| Ok(-x), Expr::UnaryMinus, scope.cloned()),
+            (Some(total), false) => evaluate_add(total, value, scope.cloned(), int),
+            (Some(total), true) => evaluate_sub(total, value, int),
+        };
+        total = match sum {
+            Ok(sum) => Some(sum),
+            Err(IntErr::Error(_)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+    }
+    let total = match total {
+        Some(total) if !failed_terms.is_empty() => total,
+        _ => return Ok(None),
+    };
+    let mut spans = vec![];
+    total.format(0, &mut spans, int)?;
+    for (negative, term) in failed_terms {
+        spans.push(Span {
+            string: if negative { " - " } else { " + " }.to_string(),
+            kind: SpanKind::Operator,
+        });
+        spans.push(Span {
+            string: term,
+            kind: SpanKind::Error,
+        });
+    }
+    Ok(Some(spans))
+}
+
+fn evaluate_comparison<'a, I: Interrupt>(
+    a: Expr<'a>,
+    comparison: Comparison,
+    b: Expr<'a>,
+    scope: Option<Arc<Scope<'a>>>,
+    context: &mut crate::Context,
+    int: &I,
+) -> Result<Value<'a>, IntErr<String, I>> {
+    let a = evaluate(a, scope.clone(), context, int)?.expect_num()?;
+    let b = evaluate(b, scope, context, int)?.expect_num()?;
+    let ordering = a.compare(b, int)?;
+    Ok(Value::Num(Number::from(u64::from(
+        comparison.holds(ordering),
+    ))))
+}
+
+// Evaluates `a and b` or `a or b`. The right-hand side is skipped if the
+// left-hand side already determines the result, e.g. `0 and (1/0)` is 0.
+fn evaluate_logical<'a, I: Interrupt>(
+    a: Expr<'a>,
+    is_or: bool,
+    b: Expr<'a>,
+    scope: Option<Arc<Scope<'a>>>,
+    context: &mut crate::Context,
+    int: &I,
+) -> Result<Value<'a>, IntErr<String, I>> {
+    let a = !evaluate(a, scope.clone(), context, int)?
+        .expect_num()?
+        .is_zero();
+    let result = if a == is_or {
+        a
+    } else {
+        !evaluate(b, scope, context, int)?.expect_num()?.is_zero()
+    };
+    Ok(Value::Num(Number::from(u64::from(result))))
+}
+
+fn evaluate_pow<'a, I: Interrupt>(
+    a: Expr<'a>,
+    b: Expr<'a>,
+    scope: Option<Arc<Scope<'a>>>,
+    context: &mut crate::Context,
+    int: &I,
+) -> Result<Value<'a>, IntErr<String, I>> {
+    let lhs = evaluate(a, scope.clone(), context, int)?;
+    if should_compute_inverse(&b) {
+        let result = match &lhs {
+            Value::BuiltInFunction(f) => Some(f.invert()?),
+            Value::Fn(_, _, _) => {
+                return Err("inverses of lambda functions are not currently supported"
+                    .to_string()
+                    .into())
+            }
+            _ => None,
+        };
+        if let Some(res) = result {
+            return Ok(res);
+        }
+    }
+    let rhs = evaluate(b, scope.clone(), context, int)?;
+    if let Value::Measurement(m) = lhs {
+        return Ok(Value::Measurement(Box::new(m.pow(rhs.expect_num()?, int)?)));
+    }
+    lhs.handle_two_nums(
+        rhs,
+        |a, b| a.pow(b, int),
+        |a| |f| Expr::Pow(f, Box::new(Expr::Num(a))),
+        |a| |f| Expr::Pow(Box::new(Expr::Num(a)), f),
+        scope,
+    )
+}
+
+fn evaluate_plus_minus<'a, I: Interrupt>(
+    a: Expr<'a>,
+    b: Expr<'a>,
+    scope: Option<Arc<Scope<'a>>>,
+    context: &mut crate::Context,
+    int: &I,
+) -> Result<Value<'a>, IntErr<String, I>> {
+    let value = evaluate(a, scope.clone(), context, int)?.expect_num()?;
+    let uncertainty = evaluate(b, scope, context, int)?.expect_num()?;
+    Ok(Value::Measurement(Box::new(Measurement::new(
+        value,
+        uncertainty,
+        int,
+    )?)))
+}
+
+fn evaluate_sub<'a, I: Interrupt>(
+    a: Value<'a>,
+    b: Value<'a>,
+    int: &I,
+) -> Result<Value<'a>, IntErr<String, I>> {
+    Ok(match (a, b) {
+        (Value::Num(a), Value::Num(b)) => Value::Num(a.sub(b, int)?),
+        (a, b) => Value::Measurement(Box::new(
+            a.expect_measurement(int)?
+                .sub(b.expect_measurement(int)?, int)?,
+        )),
+    })
+}
+
+fn evaluate_mul<'a, I: Interrupt>(
+    a: Value<'a>,
+    b: Value<'a>,
+    scope: Option<Arc<Scope<'a>>>,
+    int: &I,
+) -> Result<Value<'a>, IntErr<String, I>> {
+    if let (Value::Measurement(_), _) | (_, Value::Measurement(_)) = (&a, &b) {
+        return Ok(Value::Measurement(Box::new(
+            a.expect_measurement(int)?
+                .mul(b.expect_measurement(int)?, int)?,
+        )));
+    }
+    a.handle_two_nums(
+        b,
+        |a, b| a.mul(b, int).map_err(IntErr::into_string),
+        |a| |f| Expr::Mul(f, Box::new(Expr::Num(a))),
+        |a| |f| Expr::Mul(Box::new(Expr::Num(a)), f),
+        scope,
+    )
+}
+
+fn evaluate_div<'a, I: Interrupt>(
+    a: Value<'a>,
+    b: Value<'a>,
+    scope: Option<Arc<Scope<'a>>>,
+    int: &I,
+) -> Result<Value<'a>, IntErr<String, I>> {
+    if let (Value::Measurement(_), _) | (_, Value::Measurement(_)) = (&a, &b) {
+        return Ok(Value::Measurement(Box::new(
+            a.expect_measurement(int)?
+                .div(b.expect_measurement(int)?, int)?,
+        )));
+    }
+    a.handle_two_nums(
+        b,
+        |a, b| a.div(b, int).map_err(IntErr::into_string),
+        |a| |f| Expr::Div(f, Box::new(Expr::Num(a))),
+        |a| |f| Expr::Div(Box::new(Expr::Num(a)), f),
+        scope,
+    )
+}
+
 fn evaluate_add<'a, I: Interrupt>(
     a: Value<'a>,
     b: Value<'a>,
@@ -203,6 +468,11 @@ fn evaluate_add<'a, I: Interrupt>(
 ) -> Result<Value<'a>, IntErr<String, I>> {
     Ok(match (a, b) {
         (Value::Num(a), Value::Num(b)) => Value::Num(a.add(b, int)?),
+        (a @ Value::Measurement(_), b @ (Value::Num(_) | Value::Measurement(_)))
+        | (a @ Value::Num(_), b @ Value::Measurement(_)) => Value::Measurement(Box::new(
+            a.expect_measurement(int)?
+                .add(b.expect_measurement(int)?, int)?,
+        )),
         (Value::String(a), Value::String(b)) => {
             Value::String(format!("{}{}", a.as_ref(), b.as_ref()).into())
         }
@@ -226,6 +496,35 @@ fn evaluate_add<'a, I: Interrupt>(
     })
 }
 
+// e.g. `"2h 30m 15s" as duration` => `9015 seconds`
+fn evaluate_duration<'a, I: Interrupt>(
+    s: &str,
+    scope: Option<Arc<Scope<'a>>>,
+    context: &mut crate::Context,
+    int: &I,
+) -> Result<Number<'a>, IntErr<String, I>> {
+    let mut total: Option<Number<'a>> = None;
+    for (mantissa, decimal_places, unit) in crate::date::parse_duration(s)? {
+        let scale = 10_u64
+            .checked_pow(decimal_places)
+            .ok_or_else(|| "too many decimal places in duration".to_string())?;
+        let unit =
+            resolve_identifier(Ident::new(unit), scope.clone(), context, int)?.expect_num()?;
+        let component = Number::from(mantissa)
+            .div(Number::from(scale), int)?
+            .mul(unit, int)?;
+        total = Some(match total {
+            Some(total) => total.add(component, int)?,
+            None => component,
+        });
+    }
+    let second = resolve_identifier(Ident::new("second"), scope, context, int)?.expect_num()?;
+    match total {
+        Some(total) => total.convert_to(second, int),
+        None => Err("duration cannot be empty".to_string().into()),
+    }
+}
+
 fn evaluate_as<'a, I: Interrupt>(
     a: Expr<'a>,
     b: Expr<'a>,
@@ -245,6 +544,10 @@ fn evaluate_as<'a, I: Interrupt>(
                     Err("expected a string".to_string().into())
                 };
             }
+            "duration" => {
+                let s = evaluate(a, scope.clone(), context, int)?.expect_string()?;
+                return Ok(Value::Num(evaluate_duration(&s, scope, context, int)?));
+            }
             "string" => {
                 return Ok(Value::String(
                     evaluate(a, scope, context, int)?
@@ -270,20 +573,79 @@ fn evaluate_as<'a, I: Interrupt>(
                 }
                 return Err("expected a string".to_string().into());
             }
+            "roman" | "roman_numeral" | "roman_numerals" => {
+                let n = evaluate(a, scope, context, int)?
+                    .expect_num()?
+                    .try_as_usize(int)
+                    .map_err(IntErr::into_string)?;
+                let roman = crate::roman::to_roman(n).map_err(|e| e.to_string())?;
+                return Ok(Value::String(roman.into()));
+            }
+            "balanced_ternary" => {
+                return Value::BuiltInFunction(BuiltInFunction::ToBalancedTernary).apply(
+                    a,
+                    ApplyMulHandling::Both,
+                    scope,
+                    context,
+                    int,
+                );
+            }
+            "words" => {
+                let n = evaluate(a, scope, context, int)?.expect_num()?;
+                return Ok(Value::String(n.spell_out(int)?.into()));
+            }
+            "NATO" | "nato" => {
+                let s = evaluate(a, scope, context, int)?;
+                return Ok(Value::String(
+                    crate::nato::to_nato(&s.expect_string()?).into(),
+                ));
+            }
             _ => (),
         }
     }
-    Ok(match evaluate(b, scope.clone(), context, int)? {
-        Value::Num(b) => Value::Num(
-            evaluate(a, scope, context, int)?
-                .expect_num()?
-                .convert_to(b, int)?,
-        ),
-        Value::Format(fmt) => Value::Num(
-            evaluate(a, scope, context, int)?
-                .expect_num()?
-                .with_format(fmt),
-        ),
+    let b = evaluate(b, scope.clone(), context, int)?;
+    convert_to_value(a, b, scope, context, int)
+}
+
+// converts `a` to the unit, format or base given by `b`
+fn convert_to_value<'a, I: Interrupt>(
+    a: Expr<'a>,
+    b: Value<'a>,
+    scope: Option<Arc<Scope<'a>>>,
+    context: &mut crate::Context,
+    int: &I,
+) -> Result<Value<'a>, IntErr<String, I>> {
+    Ok(match b {
+        Value::Num(b) => match evaluate(a, scope, context, int)? {
+            Value::Measurement(a) => Value::Measurement(Box::new(a.convert_to(b, int)?)),
+            a => Value::Num(a.expect_num()?.convert_to(b, int)?),
+        },
+        Value::Format(
+            fmt @ (FormattingStyle::DegreesMinutesSeconds | FormattingStyle::HoursMinutesSeconds),
+        ) => {
+            let unit = if fmt == FormattingStyle::HoursMinutesSeconds {
+                "hour"
+            } else {
+                "degree"
+            };
+            let unit =
+                resolve_identifier(Ident::new(unit), scope.clone(), context, int)?.expect_num()?;
+            Value::Num(
+                evaluate(a, scope, context, int)?
+                    .expect_num()?
+                    .convert_to_sexagesimal(unit, fmt, int)?,
+            )
+        }
+        Value::Format(fmt @ (FormattingStyle::Clock24 | FormattingStyle::Clock12)) => {
+            let second = resolve_identifier(Ident::new("second"), scope.clone(), context, int)?
+                .expect_num()?;
+            Value::Num(
+                evaluate(a, scope, context, int)?
+                    .expect_num()?
+                    .convert_to_clock(second, fmt, int)?,
+            )
+        }
+        Value::Format(fmt) => evaluate(a, scope, context, int)?.try_with_format(fmt)?,
         Value::Dp => {
             return Err(
                 "you need to specify what number of decimal places to use, e.g. '10 dp'"
@@ -298,12 +660,8 @@ fn evaluate_as<'a, I: Interrupt>(
                     .into(),
             );
         }
-        Value::Base(base) => Value::Num(
-            evaluate(a, scope, context, int)?
-                .expect_num()?
-                .with_base(base),
-        ),
-        Value::BuiltInFunction(_) | Value::Fn(_, _, _) => {
+        Value::Base(base) => evaluate(a, scope, context, int)?.with_base(base)?,
+        Value::BuiltInFunction(_) | Value::Fn(_, _, _) | Value::Memo(_, _) => {
             return Err("unable to convert value to a function".to_string().into());
         }
         Value::Object(_) => {
@@ -315,20 +673,25 @@ fn evaluate_as<'a, I: Interrupt>(
         Value::Date(_) => {
             return Err("cannot convert value to date".to_string().into());
         }
+        Value::List(_) => {
+            return Err("cannot convert value to list".to_string().into());
+        }
+        Value::Unknown => {
+            return Err("cannot convert value to an unknown".to_string().into());
+        }
+        Value::Measurement(_) => {
+            return Err("cannot convert value to a measurement".to_string().into());
+        }
     })
 }
 
+#[allow(clippy::too_many_lines)]
 pub(crate) fn resolve_identifier<'a, I: Interrupt>(
     ident: Ident<'a>,
     scope: Option<Arc<Scope<'a>>>,
     context: &mut crate::Context,
     int: &I,
 ) -> Result<Value<'a>, IntErr<String, I>> {
-    macro_rules! eval_box {
-        ($input:expr) => {
-            Box::new(evaluate_to_value($input, scope.clone(), context, int)?)
-        };
-    }
     if let Some(scope) = scope.clone() {
         match scope.get(ident.as_str(), context, int) {
             Ok(val) => return Ok(val),
@@ -340,6 +703,14 @@ pub(crate) fn resolve_identifier<'a, I: Interrupt>(
         }
     }
     Ok(match ident.as_str() {
+        "dump_scope" => Value::Object(match scope {
+            Some(scope) => scope
+                .dump(context, int)?
+                .into_iter()
+                .map(|(ident, value)| (ident, Box::new(Value::String(value.into()))))
+                .collect(),
+            None => vec![],
+        }),
         "pi" | "\u{3c0}" => Value::Num(Number::pi()),
         "tau" | "\u{3c4}" => Value::Num(Number::pi().mul(2.into(), int)?),
         "e" => evaluate_to_value("approx. 2.718281828459045235", scope, context, int)?,
@@ -365,30 +736,137 @@ pub(crate) fn resolve_identifier<'a, I: Interrupt>(
         "log2" => Value::BuiltInFunction(BuiltInFunction::Log2),
         "log" | "log10" => Value::BuiltInFunction(BuiltInFunction::Log10),
         "exp" => evaluate_to_value("x: e^x", scope, context, int)?,
+        "series_e" => Value::BuiltInFunction(BuiltInFunction::SeriesE),
+        "help" => Value::BuiltInFunction(BuiltInFunction::Help),
+        "to_fixed" => Value::BuiltInFunction(BuiltInFunction::ToFixed),
+        "to_precision" => Value::BuiltInFunction(BuiltInFunction::ToPrecision),
+        "num_digits" => Value::BuiltInFunction(BuiltInFunction::NumDigits),
+        "reverse_digits" => Value::BuiltInFunction(BuiltInFunction::ReverseDigits),
+        "to_roman" => Value::BuiltInFunction(BuiltInFunction::ToRoman),
+        "from_roman" => Value::BuiltInFunction(BuiltInFunction::FromRoman),
+        "from_NATO" | "from_nato" => Value::BuiltInFunction(BuiltInFunction::FromNato),
+        "hex_dump" => Value::BuiltInFunction(BuiltInFunction::HexDump),
+        "hex_to_string" => Value::BuiltInFunction(BuiltInFunction::HexToString),
+        "crc32" => Value::BuiltInFunction(BuiltInFunction::Crc32),
+        "crc16" => Value::BuiltInFunction(BuiltInFunction::Crc16),
+        "djb2" => Value::BuiltInFunction(BuiltInFunction::Djb2),
+        "fnv1a" => Value::BuiltInFunction(BuiltInFunction::Fnv1a),
+        "unix_time" => Value::BuiltInFunction(BuiltInFunction::UnixTime),
+        "from_unix_time" => Value::BuiltInFunction(BuiltInFunction::FromUnixTime),
+        "lunar_phase" => Value::BuiltInFunction(BuiltInFunction::LunarPhase),
+        "season" => Value::BuiltInFunction(BuiltInFunction::Season),
+        "time_zone_offset" => Value::BuiltInFunction(BuiltInFunction::TimeZoneOffset),
+        "countdown" => Value::BuiltInFunction(BuiltInFunction::Countdown),
+        "age" => Value::BuiltInFunction(BuiltInFunction::Age),
+        "format_number" => Value::BuiltInFunction(BuiltInFunction::FormatNumber),
+        "nth" => Value::BuiltInFunction(BuiltInFunction::Nth),
+        "add" => Value::BuiltInFunction(BuiltInFunction::Add),
+        "zip" => Value::BuiltInFunction(BuiltInFunction::Zip),
+        "map" => Value::BuiltInFunction(BuiltInFunction::Map),
+        "filter" => Value::BuiltInFunction(BuiltInFunction::Filter),
+        "fold" | "reduce" => Value::BuiltInFunction(BuiltInFunction::Fold),
+        "range" => Value::BuiltInFunction(BuiltInFunction::Range),
+        "sum" => Value::BuiltInFunction(BuiltInFunction::Sum),
+        "tabulate" => Value::BuiltInFunction(BuiltInFunction::Tabulate),
+        "histogram" => Value::BuiltInFunction(BuiltInFunction::Histogram),
+        "mean" => Value::BuiltInFunction(BuiltInFunction::Mean),
+        "median" => Value::BuiltInFunction(BuiltInFunction::Median),
+        "variance" | "variance_pop" => Value::BuiltInFunction(BuiltInFunction::Variance),
+        "variance_sample" => Value::BuiltInFunction(BuiltInFunction::VarianceSample),
+        "stddev" | "stddev_pop" => Value::BuiltInFunction(BuiltInFunction::Stddev),
+        "stddev_sample" => Value::BuiltInFunction(BuiltInFunction::StddevSample),
+        "format_with_uncertainty" => Value::BuiltInFunction(BuiltInFunction::FormatWithUncertainty),
+        "bitcount" | "popcount" => Value::BuiltInFunction(BuiltInFunction::PopCount),
+        "clz" => Value::BuiltInFunction(BuiltInFunction::CountLeadingZeros),
+        "swap_bytes" => Value::BuiltInFunction(BuiltInFunction::SwapBytes),
+        "rotate_left" => Value::BuiltInFunction(BuiltInFunction::RotateLeft),
+        "rotate_right" => Value::BuiltInFunction(BuiltInFunction::RotateRight),
+        "gray_code" => Value::BuiltInFunction(BuiltInFunction::GrayCode),
+        "from_gray" => Value::BuiltInFunction(BuiltInFunction::FromGray),
+        "hamming_distance" => Value::BuiltInFunction(BuiltInFunction::HammingDistance),
+        "to_unary" => Value::BuiltInFunction(BuiltInFunction::ToUnary),
+        "from_unary" => Value::BuiltInFunction(BuiltInFunction::FromUnary),
+        "to_braille_number" => Value::BuiltInFunction(BuiltInFunction::ToBrailleNumber),
+        "from_braille_number" => Value::BuiltInFunction(BuiltInFunction::FromBrailleNumber),
+        "lcm_range" => Value::BuiltInFunction(BuiltInFunction::LcmRange),
+        "primorial" => Value::BuiltInFunction(BuiltInFunction::Primorial),
+        "collatz" => Value::BuiltInFunction(BuiltInFunction::Collatz),
+        "taxi_distance" | "manhattan_distance" => {
+            Value::BuiltInFunction(BuiltInFunction::TaxiDistance)
+        }
+        "chebyshev_distance" => Value::BuiltInFunction(BuiltInFunction::ChebyshevDistance),
+        "haversine" => Value::BuiltInFunction(BuiltInFunction::Haversine),
+        "compass_bearing" => Value::BuiltInFunction(BuiltInFunction::CompassBearing),
+        "dB_to_power_ratio" => Value::BuiltInFunction(BuiltInFunction::DecibelsToPowerRatio),
+        "power_ratio_to_dB" => Value::BuiltInFunction(BuiltInFunction::PowerRatioToDecibels),
+        "dB_to_amplitude_ratio" => {
+            Value::BuiltInFunction(BuiltInFunction::DecibelsToAmplitudeRatio)
+        }
+        "amplitude_ratio_to_dB" => {
+            Value::BuiltInFunction(BuiltInFunction::AmplitudeRatioToDecibels)
+        }
+        "VSWR_to_reflection" => Value::BuiltInFunction(BuiltInFunction::VswrToReflection),
+        "reflection_to_VSWR" => Value::BuiltInFunction(BuiltInFunction::ReflectionToVswr),
+        "ohms_law" => Value::BuiltInFunction(BuiltInFunction::OhmsLaw),
+        "parallel" | "reciprocal_sum" => Value::BuiltInFunction(BuiltInFunction::Parallel),
+        "rc_time_constant" => Value::BuiltInFunction(BuiltInFunction::RcTimeConstant),
+        "lc_resonance" => Value::BuiltInFunction(BuiltInFunction::LcResonance),
+        "snell" => Value::BuiltInFunction(BuiltInFunction::Snell),
+        "ideal_gas" => Value::BuiltInFunction(BuiltInFunction::IdealGas),
+        "ordinal_words" => Value::BuiltInFunction(BuiltInFunction::OrdinalWords),
+        "memo" => Value::BuiltInFunction(BuiltInFunction::Memo),
+        "integer_part" => Value::BuiltInFunction(BuiltInFunction::IntegerPart),
+        "fractional_part" => Value::BuiltInFunction(BuiltInFunction::FractionalPart),
+        "to_balanced_ternary" => Value::BuiltInFunction(BuiltInFunction::ToBalancedTernary),
+        "is_leap_year" => Value::BuiltInFunction(BuiltInFunction::IsLeapYear),
+        "days_in_year" => Value::BuiltInFunction(BuiltInFunction::DaysInYear),
+        "easter" | "easter_western" => Value::BuiltInFunction(BuiltInFunction::Easter),
+        "easter_orthodox" => Value::BuiltInFunction(BuiltInFunction::EasterOrthodox),
+        "convert_currency" => Value::BuiltInFunction(BuiltInFunction::ConvertCurrency),
+        "exponent" => Value::BuiltInFunction(BuiltInFunction::Exponent),
+        "mantissa" => Value::BuiltInFunction(BuiltInFunction::Mantissa),
+        "apply_at" => Value::BuiltInFunction(BuiltInFunction::ApplyAt),
+        "compose" => Value::BuiltInFunction(BuiltInFunction::Compose),
+        "convergents" => Value::BuiltInFunction(BuiltInFunction::Convergents),
+        "bernstein" => Value::BuiltInFunction(BuiltInFunction::Bernstein),
+        "bcd" | "to_bcd" => Value::BuiltInFunction(BuiltInFunction::Bcd),
+        "from_bcd" => Value::BuiltInFunction(BuiltInFunction::FromBcd),
+        "interleave" => Value::BuiltInFunction(BuiltInFunction::Interleave),
         "approx." | "approximately" => Value::BuiltInFunction(BuiltInFunction::Approximately),
         "auto" => Value::Format(FormattingStyle::Auto),
         "exact" => Value::Format(FormattingStyle::Exact),
         "frac" | "fraction" => Value::Format(FormattingStyle::ImproperFraction),
+        "ratio" => Value::Format(FormattingStyle::AlwaysRatio),
         "mixed_fraction" => Value::Format(FormattingStyle::MixedFraction),
         "float" => Value::Format(FormattingStyle::ExactFloat),
+        "currency" => Value::Format(FormattingStyle::Currency),
+        "dms" => Value::Format(FormattingStyle::DegreesMinutesSeconds),
+        "hms" => Value::Format(FormattingStyle::HoursMinutesSeconds),
+        "clock" | "clock_24h" => Value::Format(FormattingStyle::Clock24),
+        "clock_12h" => Value::Format(FormattingStyle::Clock12),
+        "engineering_prefixed" => Value::Format(FormattingStyle::EngineeringPrefixed),
         "dp" => Value::Dp,
         "sf" => Value::Sf,
+        "?" => Value::Unknown,
         "base" => Value::BuiltInFunction(BuiltInFunction::Base),
         "dec" | "decimal" => Value::Base(Base::from_plain_base(10).map_err(|e| e.to_string())?),
         "hex" | "hexadecimal" => Value::Base(Base::from_plain_base(16).map_err(|e| e.to_string())?),
         "binary" => Value::Base(Base::from_plain_base(2).map_err(|e| e.to_string())?),
         "oct" | "octal" => Value::Base(Base::from_plain_base(8).map_err(|e| e.to_string())?),
         "version" => Value::String(crate::get_version_as_str().into()),
+        "version_info" => {
+            let (major, minor, patch, pre_release) = crate::get_version_components();
+            Value::Object(vec![
+                ("major", Box::new(Value::Num(Number::from(major)))),
+                ("minor", Box::new(Value::Num(Number::from(minor)))),
+                ("patch", Box::new(Value::Num(Number::from(patch)))),
+                ("pre_release", Box::new(Value::String(pre_release.into()))),
+            ])
+        }
         "square" => evaluate_to_value("x: x^2", scope, context, int)?,
         "cubic" => evaluate_to_value("x: x^3", scope, context, int)?,
-        "earth" => Value::Object(vec![
-            ("axial_tilt", eval_box!("23.4392811 degrees")),
-            ("eccentricity", eval_box!("0.0167086")),
-            ("escape_velocity", eval_box!("11.186 km/s")),
-            ("gravity", eval_box!("9.80665 m/s^2")),
-            ("mass", eval_box!("5.97237e24 kg")),
-            ("volume", eval_box!("1.08321e12 km^3")),
-        ]),
+        "earth" | "moon" | "sun" | "mars" | "venus" | "jupiter" | "electron" | "proton"
+        | "neutron" => resolve_object(ident.as_str(), scope.as_ref(), context, int)?,
         "differentiate" => Value::BuiltInFunction(BuiltInFunction::Differentiate),
         "today" => Value::Date(crate::date::Date::today(context).map_err(|e| e.to_string())?),
         "tomorrow" => Value::Date(
@@ -407,3 +885,90 @@ pub(crate) fn resolve_identifier<'a, I: Interrupt>(
         }
     })
 }
+
+// built-in objects like `earth` or `electron`, kept separate from
+// `resolve_identifier` to keep its stack frame small
+fn resolve_object<'a, I: Interrupt>(
+    name: &str,
+    scope: Option<&Arc<Scope<'a>>>,
+    context: &mut crate::Context,
+    int: &I,
+) -> Result<Value<'a>, IntErr<String, I>> {
+    macro_rules! eval_box {
+        ($input:expr) => {
+            Box::new(evaluate_to_value($input, scope.cloned(), context, int)?)
+        };
+    }
+    Ok(match name {
+        "earth" => Value::Object(vec![
+            ("atmospheric_pressure", eval_box!("101325 Pa")),
+            ("axial_tilt", eval_box!("23.4392811 degrees")),
+            ("circumference", eval_box!("40075.017 km")),
+            ("diameter", eval_box!("12742 km")),
+            ("eccentricity", eval_box!("0.0167086")),
+            ("escape_velocity", eval_box!("11.186 km/s")),
+            ("gravity", eval_box!("9.80665 m/s^2")),
+            ("mass", eval_box!("5.97237e24 kg")),
+            ("orbital_period", eval_box!("365.256363004 days")),
+            ("radius", eval_box!("6371 km")),
+            ("rotation_period", eval_box!("86164.0905 s")),
+            ("solar_day", eval_box!("86400 s")),
+            ("surface_area", eval_box!("510072000 km^2")),
+            ("volume", eval_box!("1.08321e12 km^3")),
+        ]),
+        "moon" => Value::Object(vec![
+            ("escape_velocity", eval_box!("2.38 km/s")),
+            ("gravity", eval_box!("1.62 m/s^2")),
+            ("mass", eval_box!("7.346e22 kg")),
+            ("orbital_period", eval_box!("27.321661 days")),
+            ("radius", eval_box!("1737.4 km")),
+        ]),
+        "sun" => Value::Object(vec![
+            ("escape_velocity", eval_box!("617.6 km/s")),
+            ("gravity", eval_box!("274 m/s^2")),
+            ("mass", eval_box!("1.9885e30 kg")),
+            ("orbital_period", eval_box!("2.3e8 years")),
+            ("radius", eval_box!("695700 km")),
+        ]),
+        "mars" => Value::Object(vec![
+            ("escape_velocity", eval_box!("5.03 km/s")),
+            ("gravity", eval_box!("3.73 m/s^2")),
+            ("mass", eval_box!("6.4171e23 kg")),
+            ("orbital_period", eval_box!("686.98 days")),
+            ("radius", eval_box!("3389.5 km")),
+        ]),
+        "venus" => Value::Object(vec![
+            ("escape_velocity", eval_box!("10.36 km/s")),
+            ("gravity", eval_box!("8.87 m/s^2")),
+            ("mass", eval_box!("4.8675e24 kg")),
+            ("orbital_period", eval_box!("224.701 days")),
+            ("radius", eval_box!("6051.8 km")),
+        ]),
+        "jupiter" => Value::Object(vec![
+            ("escape_velocity", eval_box!("59.5 km/s")),
+            ("gravity", eval_box!("24.79 m/s^2")),
+            ("mass", eval_box!("1.8982e27 kg")),
+            ("orbital_period", eval_box!("4332.59 days")),
+            ("radius", eval_box!("69911 km")),
+        ]),
+        "electron" => Value::Object(vec![
+            ("charge", eval_box!("-electron_charge")),
+            ("magnetic_moment", eval_box!("-9.2847647043e-24 J/T")),
+            ("mass", eval_box!("9.1093837015e-31 kg")),
+            ("spin", eval_box!("1/2")),
+        ]),
+        "proton" => Value::Object(vec![
+            ("charge", eval_box!("electron_charge")),
+            ("magnetic_moment", eval_box!("1.41060679736e-26 J/T")),
+            ("mass", eval_box!("1.67262192369e-27 kg")),
+            ("spin", eval_box!("1/2")),
+        ]),
+        "neutron" => Value::Object(vec![
+            ("charge", eval_box!("0 coulomb")),
+            ("magnetic_moment", eval_box!("-9.6623651e-27 J/T")),
+            ("mass", eval_box!("1.67492749804e-27 kg")),
+            ("spin", eval_box!("1/2")),
+        ]),
+        _ => return Err(format!("unknown object '{}'", name).into()),
+    })
+}
@@ -0,0 +1,24 @@
+/// Converts an integer to balanced ternary, which uses the digits
+/// `1`, `0` and `T` (for -1), e.g. 5 becomes `1TT` (9 - 3 - 1)
+pub(crate) fn to_balanced_ternary(n: i64) -> String {
+    if n == 0 {
+        return "0".to_string();
+    }
+    let mut n = i128::from(n);
+    let mut digits = vec![];
+    while n != 0 {
+        match n.rem_euclid(3) {
+            0 => digits.push('0'),
+            1 => {
+                digits.push('1');
+                n -= 1;
+            }
+            _ => {
+                digits.push('T');
+                n += 1;
+            }
+        }
+        n /= 3;
+    }
+    digits.iter().rev().collect()
+}
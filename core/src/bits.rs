@@ -0,0 +1,60 @@
+// Bit manipulation on integers with a fixed bit width of at most 64 bits
+
+fn check_width(n: u64, width: u32) -> Result<(), String> {
+    if width == 0 || width > 64 {
+        return Err("bit width must be between 1 and 64".to_string());
+    }
+    if width < 64 && n >> width != 0 {
+        return Err(format!("{} does not fit into {} bits", n, width));
+    }
+    Ok(())
+}
+
+/// Counts the leading zeros of `n` when written with `width` bits,
+/// e.g. `0b0001` has 3 leading zeros as a 4-bit number
+pub(crate) fn leading_zeros(n: u64, width: u32) -> Result<u32, String> {
+    check_width(n, width)?;
+    Ok(n.leading_zeros() - (64 - width))
+}
+
+/// Reverses the order of the lowest `bytes` bytes of `n`, e.g. to convert
+/// between big-endian and little-endian
+pub(crate) fn swap_bytes(n: u64, bytes: u32) -> Result<u64, String> {
+    if !matches!(bytes, 1 | 2 | 4 | 8) {
+        return Err("number of bytes must be 1, 2, 4 or 8".to_string());
+    }
+    check_width(n, bytes * 8)?;
+    Ok(n.swap_bytes() >> (64 - bytes * 8))
+}
+
+/// Rotates the lowest `width` bits of `n` by `bits` positions, to the left
+/// if `rotate_left` is true and to the right otherwise
+pub(crate) fn rotate(n: u64, bits: u64, width: u32, rotate_left: bool) -> Result<u64, String> {
+    check_width(n, width)?;
+    let width_u64 = u64::from(width);
+    let mut bits = bits % width_u64;
+    if !rotate_left {
+        bits = (width_u64 - bits) % width_u64;
+    }
+    if bits == 0 {
+        return Ok(n);
+    }
+    let mask = u64::MAX >> (64 - width);
+    Ok(((n << bits) | (n >> (width_u64 - bits))) & mask)
+}
+
+/// Converts `n` to its reflected binary Gray code, e.g. 6 (`110`)
+/// becomes 5 (`101`)
+pub(crate) fn gray_code(n: u64) -> u64 {
+    n ^ (n >> 1)
+}
+
+/// Converts a reflected binary Gray code back to an ordinary integer
+pub(crate) fn from_gray(mut n: u64) -> u64 {
+    let mut result = 0;
+    while n != 0 {
+        result ^= n;
+        n >>= 1;
+    }
+    result
+}
@@ -0,0 +1,41 @@
+const NUMBER_INDICATOR: char = '\u{283c}';
+
+// the Braille cells for the digits 0 to 9, which are the same as the
+// cells for the letters `j` and `a` to `i`
+const DIGITS: [char; 10] = [
+    '\u{281a}', '\u{2801}', '\u{2803}', '\u{2809}', '\u{2819}', '\u{2811}', '\u{280b}', '\u{281b}',
+    '\u{2813}', '\u{280a}',
+];
+
+/// Writes a non-negative integer in Braille, e.g. 42 becomes `⠼⠙⠃`
+pub(crate) fn to_braille_number(n: u64) -> String {
+    let mut result = String::new();
+    result.push(NUMBER_INDICATOR);
+    for digit in n.to_string().bytes() {
+        result.push(DIGITS[usize::from(digit - b'0')]);
+    }
+    result
+}
+
+/// Parses a Braille number, which must start with the number indicator `⠼`
+pub(crate) fn from_braille_number(s: &str) -> Result<u64, String> {
+    let digits = s
+        .trim()
+        .strip_prefix(NUMBER_INDICATOR)
+        .ok_or_else(|| "Braille numbers must start with the number indicator".to_string())?;
+    if digits.is_empty() {
+        return Err("invalid Braille number".to_string());
+    }
+    let mut result: u64 = 0;
+    for ch in digits.chars() {
+        let digit = DIGITS
+            .iter()
+            .position(|&d| d == ch)
+            .ok_or_else(|| format!("invalid Braille digit '{}'", ch))?;
+        result = result
+            .checked_mul(10)
+            .and_then(|r| r.checked_add(digit as u64))
+            .ok_or_else(|| "Braille number is too large".to_string())?;
+    }
+    Ok(result)
+}
@@ -0,0 +1,63 @@
+// Both checksums use the reflected (least significant bit first) form of
+// their polynomial, so the lookup tables are indexed by the low byte.
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i: u32 = 0;
+    while i < 256 {
+        let mut crc = i;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i as usize] = crc;
+        i += 1;
+    }
+    table
+}
+
+const fn crc16_table() -> [u16; 256] {
+    let mut table = [0; 256];
+    let mut i: u16 = 0;
+    while i < 256 {
+        let mut crc = i;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xa001
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i as usize] = crc;
+        i += 1;
+    }
+    table
+}
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+const CRC16_TABLE: [u16; 256] = crc16_table();
+
+/// CRC-32 (as used by zlib, PNG and Ethernet), e.g. `hello` => `0x3610a686`
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff;
+    for &byte in bytes {
+        crc = (crc >> 8) ^ CRC32_TABLE[(crc ^ u32::from(byte)) as usize & 0xff];
+    }
+    !crc
+}
+
+/// CRC-16/ARC, e.g. `hello` => `0x34d2`
+pub(crate) fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc = 0;
+    for &byte in bytes {
+        crc = (crc >> 8) ^ CRC16_TABLE[usize::from((crc ^ u16::from(byte)) & 0xff)];
+    }
+    crc
+}
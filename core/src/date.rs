@@ -1,13 +1,16 @@
-use std::fmt;
+use std::{convert, fmt};
 
 mod day;
 mod day_of_week;
+mod duration;
 mod month;
 mod parser;
 mod year;
 
+use crate::value::Value;
 use day::Day;
 use day_of_week::DayOfWeek;
+pub(crate) use duration::parse_duration;
 use month::Month;
 use year::Year;
 
@@ -20,6 +23,14 @@ pub(crate) struct Date {
 
 pub(crate) struct TodayError;
 
+pub(crate) struct DateOutOfRangeError;
+
+impl fmt::Display for DateOutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "date is out of range")
+    }
+}
+
 impl fmt::Display for TodayError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "unable to get the current date")
@@ -27,37 +38,205 @@ impl fmt::Display for TodayError {
 }
 
 impl Date {
-    #[allow(
-        clippy::cast_possible_truncation,
-        clippy::cast_sign_loss,
-        clippy::cast_possible_wrap
-    )]
     pub(crate) fn today(context: &mut crate::Context) -> Result<Self, TodayError> {
         let current_time_info = if let Some(t) = &context.current_time {
             t
         } else {
             return Err(TodayError);
         };
-        let mut ms_since_epoch = current_time_info.elapsed_unix_time_ms as i64;
-        ms_since_epoch -= current_time_info.timezone_offset_secs * 1000;
-        let mut days = ms_since_epoch / 86_400_000; // no leap seconds
-        let mut year = Year::new(1970);
-        while days >= year.number_of_days().into() {
-            year = year.next();
-            days -= i64::from(year.number_of_days());
+        let ms_since_epoch = i128::from(current_time_info.elapsed_unix_time_ms)
+            - i128::from(current_time_info.timezone_offset_secs) * 1000;
+        let days = convert::TryInto::try_into(ms_since_epoch.div_euclid(86_400_000)) // no leap seconds
+            .map_err(|_| TodayError)?;
+        Self::from_days_since_epoch(days).map_err(|_| TodayError)
+    }
+
+    /// Returns the number of days since 1 January 1970 (which may be negative)
+    pub(crate) fn days_since_epoch(self) -> i64 {
+        // see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let mut year = i64::from(self.year.value());
+        // there is no year 0, so 1 BC is year 0 in this calculation
+        if year < 0 {
+            year += 1;
         }
-        let mut month = Month::January;
-        while days >= month.number_of_days(year).into() {
-            month = month.next();
-            days -= i64::from(month.number_of_days(year));
+        let month = i64::from(self.month.number());
+        if month <= 2 {
+            year -= 1;
+        }
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5
+            + i64::from(self.day.value())
+            - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Returns the Julian day number, i.e. the number of days since
+    /// noon on 1 January 4713 BC (in the proleptic Julian calendar)
+    pub(crate) fn to_julian_day_number(self) -> i64 {
+        self.days_since_epoch() + 2_440_588
+    }
+
+    /// Returns the approximate phase of the moon (at noon UTC) as one of eight names
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(crate) fn lunar_phase(self) -> &'static str {
+        // all times are in units of 10^-11 days
+        const SYNODIC_MONTH: i128 = 2_953_058_770_576;
+        // a new moon occurred at Julian day 2451550.1 (6 January 2000)
+        const NEW_MOON: i128 = 245_155_010_000_000_000;
+        const PHASES: [&str; 8] = [
+            "New Moon",
+            "Waxing Crescent",
+            "First Quarter",
+            "Waxing Gibbous",
+            "Full Moon",
+            "Waning Gibbous",
+            "Last Quarter",
+            "Waning Crescent",
+        ];
+        let time = i128::from(self.to_julian_day_number()) * 100_000_000_000;
+        let age = (time - NEW_MOON).rem_euclid(SYNODIC_MONTH);
+        // round to the nearest eighth of a lunar cycle
+        let idx = (age * 16 + SYNODIC_MONTH) / (2 * SYNODIC_MONTH) % 8;
+        PHASES[idx as usize]
+    }
+
+    /// Returns the meteorological season, where e.g. winter is
+    /// December to February in the northern hemisphere
+    pub(crate) fn season(self, southern_hemisphere: bool) -> &'static str {
+        let northern = match self.month {
+            Month::December | Month::January | Month::February => 0,
+            Month::March | Month::April | Month::May => 1,
+            Month::June | Month::July | Month::August => 2,
+            Month::September | Month::October | Month::November => 3,
+        };
+        let idx = if southern_hemisphere {
+            (northern + 2) % 4
+        } else {
+            northern
+        };
+        ["Winter", "Spring", "Summer", "Autumn"][idx]
+    }
+
+    // number of months since January of 1 BC
+    fn month_index(self) -> i64 {
+        let mut year = i64::from(self.year.value());
+        if year < 0 {
+            year += 1;
+        }
+        year * 12 + i64::from(self.month.number()) - 1
+    }
+
+    // adds a number of months, using the last day of the month if the
+    // day doesn't exist in that month (e.g. 31 January + 1 month => 28 February)
+    fn add_months(self, months: i64) -> Result<Self, DateOutOfRangeError> {
+        let idx = self.month_index() + months;
+        let mut year = idx.div_euclid(12);
+        if year <= 0 {
+            year -= 1;
         }
+        let year = Year::new(convert::TryInto::try_into(year).map_err(|_| DateOutOfRangeError)?);
+        let month: i32 =
+            convert::TryInto::try_into(idx.rem_euclid(12) + 1).map_err(|_| DateOutOfRangeError)?;
+        let month: Month = convert::TryInto::try_into(month).map_err(|_| DateOutOfRangeError)?;
+        let day = Day::new(self.day.value().min(month.number_of_days(year)));
+        Ok(Self { year, month, day })
+    }
+
+    /// Returns the whole years, months and remaining days from this date
+    /// until the given (later) date
+    pub(crate) fn age_at(self, today: Self) -> Result<(i64, i64, i64), String> {
+        if self.days_since_epoch() > today.days_since_epoch() {
+            return Err("date is in the future".to_string());
+        }
+        let mut months = today.month_index() - self.month_index();
+        let mut anniversary = self.add_months(months).map_err(|e| e.to_string())?;
+        if anniversary.days_since_epoch() > today.days_since_epoch() {
+            months -= 1;
+            anniversary = self.add_months(months).map_err(|e| e.to_string())?;
+        }
+        let days = today.days_since_epoch() - anniversary.days_since_epoch();
+        Ok((months / 12, months % 12, days))
+    }
+
+    /// Returns the date that is the given number of days after 1 January 1970
+    pub(crate) fn from_days_since_epoch(days: i64) -> Result<Self, DateOutOfRangeError> {
+        // see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = days.checked_add(719_468).ok_or(DateOutOfRangeError)?;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let mut year = era
+            .checked_mul(400)
+            .and_then(|y| y.checked_add(year_of_era))
+            .ok_or(DateOutOfRangeError)?;
+        if month <= 2 {
+            year += 1;
+        }
+        if year <= 0 {
+            year -= 1;
+        }
+        let year: i32 = convert::TryInto::try_into(year).map_err(|_| DateOutOfRangeError)?;
+        let month: i32 = convert::TryInto::try_into(month).map_err(|_| DateOutOfRangeError)?;
+        let day: u8 = convert::TryInto::try_into(day).map_err(|_| DateOutOfRangeError)?;
         Ok(Self {
-            year,
-            month,
-            day: Day::new(days as u8),
+            year: Year::new(year),
+            month: convert::TryInto::try_into(month).map_err(|_| DateOutOfRangeError)?,
+            day: Day::new(day),
         })
     }
 
+    /// Returns the date of Easter Sunday in the given year. Western Easter
+    /// uses the anonymous Gregorian algorithm, while Orthodox Easter is
+    /// calculated in the Julian calendar and then converted to a Gregorian date.
+    pub(crate) fn easter(year: i64, orthodox: bool) -> Result<Self, String> {
+        if year < 1583 {
+            return Err("Easter can only be calculated for years from 1583 onwards".to_string());
+        }
+        let days = if orthodox {
+            // see Meeus, Astronomical Algorithms, chapter 8
+            let epact = (19 * (year % 19) + 15) % 30;
+            let weekday = (2 * (year % 4) + 4 * (year % 7) - epact + 34) % 7;
+            epact + weekday + 114
+        } else {
+            let golden_number = year % 19;
+            let (century, year_of_century) = (year / 100, year % 100);
+            let lunar_correction = (century - (century + 8) / 25 + 1) / 3;
+            let epact = (19 * golden_number + century - century / 4 - lunar_correction + 15) % 30;
+            let weekday =
+                (32 + 2 * (century % 4) + 2 * (year_of_century / 4) - epact - year_of_century % 4)
+                    % 7;
+            let correction = (golden_number + 11 * epact + 22 * weekday) / 451;
+            epact + weekday - 7 * correction + 114
+        };
+        let (month, day) = (days / 31, days % 31 + 1);
+        let year: i32 = convert::TryInto::try_into(year).map_err(|_| "year is out of range")?;
+        let month: i32 = convert::TryInto::try_into(month).map_err(|_| "invalid month")?;
+        let day: u8 = convert::TryInto::try_into(day).map_err(|_| "invalid day")?;
+        let date = Self {
+            year: Year::new(year),
+            month: convert::TryInto::try_into(month).map_err(|_| "invalid month")?,
+            day: Day::new(day),
+        };
+        if !orthodox {
+            return Ok(date);
+        }
+        // the difference between the Julian and Gregorian calendars,
+        // e.g. 13 days from 1900 to 2099
+        let offset = i64::from(year / 100 - year / 400 - 2);
+        Self::from_days_since_epoch(date.days_since_epoch() + offset).map_err(|e| e.to_string())
+    }
+
     fn day_of_week(self) -> DayOfWeek {
         let d1 = (1
             + 5 * ((self.year.value() - 1) % 4)
@@ -133,11 +312,39 @@ impl Date {
         }
     }
 
+    /// Returns the day number within the year, from 1 to 366
+    fn day_of_year(self) -> u16 {
+        let mut day = u16::from(self.day.value());
+        let mut month = Month::January;
+        while month != self.month {
+            day += u16::from(month.number_of_days(self.year));
+            month = month.next();
+        }
+        day
+    }
+
+    pub(crate) fn get_object_member<'a>(self, key: &str) -> Result<Value<'a>, &'static str> {
+        Ok(match key {
+            "day_of_year" => Value::Num(u64::from(self.day_of_year()).into()),
+            "days_in_month" => Value::Num(u64::from(self.month.number_of_days(self.year)).into()),
+            "days_in_year" => Value::Num(u64::from(self.year.number_of_days()).into()),
+            _ => return Err("could not find key in date"),
+        })
+    }
+
     pub(crate) fn parse(s: &str) -> Result<Self, parser::ParseDateError<'_>> {
         parser::parse_date(s)
     }
 }
 
+/// Returns the number of days (365 or 366) in the given Gregorian year,
+/// where negative years are BC and there is no year 0
+pub(crate) fn days_in_year(year: i64) -> Result<u16, String> {
+    let year: i32 = convert::TryInto::try_into(year).map_err(|_| "year is out of range")?;
+    let year: Year = convert::TryInto::try_into(year).map_err(|_| "year 0 does not exist")?;
+    Ok(year.number_of_days())
+}
+
 impl fmt::Debug for Date {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -0,0 +1,72 @@
+/// One part of a duration like `3.5 hours`, stored as the digits `35`,
+/// the number of decimal places (here 1) and the name of the unit.
+pub(crate) type DurationComponent = (u64, u32, &'static str);
+
+fn parse_unit(name: &str) -> Option<&'static str> {
+    Some(match name.to_lowercase().as_str() {
+        "w" | "wk" | "wks" | "week" | "weeks" => "week",
+        "d" | "day" | "days" => "day",
+        "h" | "hr" | "hrs" | "hour" | "hours" => "hour",
+        "m" | "min" | "mins" | "minute" | "minutes" => "minute",
+        "s" | "sec" | "secs" | "second" | "seconds" => "second",
+        "ms" | "msec" | "msecs" | "millisecond" | "milliseconds" => "millisecond",
+        _ => return None,
+    })
+}
+
+fn parse_number(s: &str) -> Result<(u64, u32), String> {
+    let (integer_part, fractional_part) = match s.find('.') {
+        Some(idx) => (&s[..idx], &s[idx + 1..]),
+        None => (s, ""),
+    };
+    if integer_part.is_empty() && fractional_part.is_empty() {
+        return Err(format!("invalid number '{}' in duration", s));
+    }
+    let digits = format!("{}{}", integer_part, fractional_part);
+    let mantissa = digits
+        .parse()
+        .map_err(|_| format!("invalid number '{}' in duration", s))?;
+    let decimal_places = std::convert::TryInto::try_into(fractional_part.len())
+        .map_err(|_| format!("invalid number '{}' in duration", s))?;
+    Ok((mantissa, decimal_places))
+}
+
+/// Parses human-readable durations like `2h 30m 15s`, `1 day, 2 hours`
+/// or `3.5 hours` into their components.
+pub(crate) fn parse_duration(s: &str) -> Result<Vec<DurationComponent>, String> {
+    let mut components = vec![];
+    let mut remaining = s.trim();
+    while !remaining.is_empty() {
+        let number_len = remaining
+            .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+            .unwrap_or(remaining.len());
+        if number_len == 0 {
+            return Err(format!("expected a number in duration '{}'", s));
+        }
+        let (mantissa, decimal_places) = parse_number(&remaining[..number_len])?;
+        remaining = remaining[number_len..].trim_start();
+        let unit_len = remaining
+            .find(|ch: char| !ch.is_alphabetic())
+            .unwrap_or(remaining.len());
+        let unit_name = &remaining[..unit_len];
+        if unit_name.is_empty() {
+            return Err(format!("missing unit in duration '{}'", s));
+        }
+        let unit = parse_unit(unit_name)
+            .ok_or_else(|| format!("unknown duration unit '{}'", unit_name))?;
+        components.push((mantissa, decimal_places, unit));
+        remaining = remaining[unit_len..].trim_start();
+        // components may be separated by commas or `and`
+        remaining = remaining
+            .strip_prefix(',')
+            .unwrap_or(remaining)
+            .trim_start();
+        if let Some(rest) = remaining.strip_prefix("and ") {
+            remaining = rest.trim_start();
+        }
+    }
+    if components.is_empty() {
+        return Err("duration cannot be empty".to_string());
+    }
+    Ok(components)
+}
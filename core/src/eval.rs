@@ -1,13 +1,16 @@
 use std::sync::Arc;
 
-use crate::{Span, ast, error::{IntErr, Interrupt}, lexer::{self, Symbol, Token}, parser, scope::Scope, value::Value};
+use crate::{
+    ast,
+    error::{IntErr, Interrupt},
+    lexer::{self, Symbol, Token},
+    parser,
+    scope::Scope,
+    value::Value,
+    Span,
+};
 
-pub(crate) fn evaluate_to_value<'a, I: Interrupt>(
-    input: &'a str,
-    scope: Option<Arc<Scope<'a>>>,
-    context: &mut crate::Context,
-    int: &I,
-) -> Result<Value<'a>, IntErr<String, I>> {
+fn parse<'a, I: Interrupt>(input: &'a str, int: &I) -> Result<ast::Expr<'a>, IntErr<String, I>> {
     //eprintln!("input {}", input);
     let lex = lexer::lex(input, int);
     let mut tokens = vec![];
@@ -24,9 +27,11 @@ pub(crate) fn evaluate_to_value<'a, I: Interrupt>(
         let mut i = 1;
         while i < tokens.len() - 1 {
             if matches!(tokens[i - 1], Token::Symbol(Symbol::Div))
-                && matches!(tokens[i], Token::Num(_)) && matches!(tokens[i+1], Token::Ident(_)) {
+                && matches!(tokens[i], Token::Num(_))
+                && matches!(tokens[i + 1], Token::Ident(_))
+            {
                 //eprintln!("inserting stuff @ {}", i);
-                tokens.insert(i+2, Token::Symbol(Symbol::CloseParens));
+                tokens.insert(i + 2, Token::Symbol(Symbol::CloseParens));
                 tokens.insert(i, Token::Symbol(Symbol::OpenParens));
                 i += 2;
             }
@@ -37,27 +42,59 @@ pub(crate) fn evaluate_to_value<'a, I: Interrupt>(
     for _ in 0..missing_open_parens {
         tokens.insert(0, lexer::Token::Symbol(lexer::Symbol::OpenParens));
     }
-    let parsed = parser::parse_tokens(&tokens).map_err(|e| e.to_string())?;
+    parser::parse_tokens(&tokens).map_err(|e| e.to_string().into())
+}
+
+pub(crate) fn evaluate_to_value<'a, I: Interrupt>(
+    input: &'a str,
+    scope: Option<Arc<Scope<'a>>>,
+    context: &mut crate::Context,
+    int: &I,
+) -> Result<Value<'a>, IntErr<String, I>> {
+    let parsed = parse(input, int)?;
     let result = ast::evaluate(parsed, scope, context, int)?;
     Ok(result)
 }
 
+/// The value of a numeric result, which is only converted to a fraction
+/// if `FendResult::get_rational_approximation()` is called
+pub(crate) type RationalApproximation = Option<crate::num::Complex>;
+
 pub(crate) fn evaluate_to_spans<'a, I: Interrupt>(
     mut input: &'a str,
     scope: Option<Arc<Scope<'a>>>,
     context: &mut crate::Context,
     int: &I,
-) -> Result<Vec<Span>, IntErr<String, I>> {
+) -> Result<(Vec<Span>, RationalApproximation), IntErr<String, I>> {
     let debug = input.strip_prefix("!debug ").map_or(false, |remaining| {
         input = remaining;
         true
     });
-    let value = evaluate_to_value(input, scope, context, int)?;
-    Ok(if debug {
-        vec![Span::from_string(format!("{:?}", value))]
+    let parsed = parse(input, int)?;
+    let value = if context.partial_evaluation {
+        match ast::evaluate(parsed.clone(), scope.clone(), context, int) {
+            Err(IntErr::Error(e)) => {
+                return match ast::evaluate_partially(parsed, scope.as_ref(), context, int)? {
+                    Some(spans) => Ok((spans, None)),
+                    None => Err(e.into()),
+                };
+            }
+            result => result?,
+        }
     } else {
-        let mut spans = vec![];
-        value.format(0, &mut spans, int)?;
-        spans
+        ast::evaluate(parsed, scope, context, int)?
+    };
+    let mut spans = vec![];
+    Ok(match value {
+        value if debug => (vec![Span::from_string(format!("{:?}", value))], None),
+        Value::Num(n) => {
+            let n = n.simplify(int)?;
+            n.format(int)?.spans(&mut spans);
+            (spans, Some(n.into_complex()))
+        }
+        value => {
+            value.format(0, &mut spans, int)?;
+            (spans, None)
+        }
     })
 }
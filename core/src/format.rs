@@ -2,6 +2,8 @@ use crate::error::{IntErr, Interrupt};
 use crate::num::Exact;
 use std::fmt;
 
+pub(crate) mod template;
+
 pub(crate) trait Format {
     type Params: Default;
     type Out: fmt::Display + fmt::Debug;
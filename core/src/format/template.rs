@@ -0,0 +1,92 @@
+/// A number format template like `#,##0.00`, where `0` is a required
+/// digit, `#` an optional digit, `,` enables grouping of thousands and
+/// `.` is the decimal separator. Any other characters before or after
+/// the digits (e.g. a currency symbol) are kept as they are.
+pub(crate) struct Template {
+    prefix: String,
+    suffix: String,
+    min_integer_digits: usize,
+    grouping: bool,
+    min_decimal_places: usize,
+    max_decimal_places: usize,
+}
+
+fn is_pattern_char(ch: char) -> bool {
+    matches!(ch, '#' | '0' | ',' | '.')
+}
+
+impl Template {
+    pub(crate) fn parse(template: &str) -> Result<Self, String> {
+        let start = template
+            .find(is_pattern_char)
+            .ok_or_else(|| "number format template must contain '#' or '0'".to_string())?;
+        let end = template[start..]
+            .find(|ch| !is_pattern_char(ch))
+            .map_or(template.len(), |idx| start + idx);
+        let pattern = &template[start..end];
+        let (integer_pattern, decimal_pattern) = match pattern.find('.') {
+            Some(idx) => (&pattern[..idx], &pattern[idx + 1..]),
+            None => (pattern, ""),
+        };
+        if decimal_pattern.contains(&['.', ','][..]) {
+            return Err("invalid decimal places in number format template".to_string());
+        }
+        if !pattern.contains(&['#', '0'][..]) {
+            return Err("number format template must contain '#' or '0'".to_string());
+        }
+        Ok(Self {
+            prefix: template[..start].to_string(),
+            suffix: template[end..].to_string(),
+            min_integer_digits: integer_pattern.matches('0').count(),
+            grouping: integer_pattern.contains(','),
+            min_decimal_places: decimal_pattern.matches('0').count(),
+            max_decimal_places: decimal_pattern.len(),
+        })
+    }
+
+    pub(crate) fn max_decimal_places(&self) -> usize {
+        self.max_decimal_places
+    }
+
+    /// Applies the template to a number that has already been rounded
+    /// to `max_decimal_places`, e.g. `-1234.50 kg`
+    pub(crate) fn apply(&self, formatted: &str) -> String {
+        let (sign, formatted) = match formatted.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", formatted),
+        };
+        let number_len = formatted
+            .find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '.')
+            .unwrap_or(formatted.len());
+        let (number, unit) = formatted.split_at(number_len);
+        let (integer_part, decimal_part) = match number.find('.') {
+            Some(idx) => (&number[..idx], &number[idx + 1..]),
+            None => (number, ""),
+        };
+        let integer_part = integer_part.trim_start_matches('0');
+        let mut integer_digits =
+            "0".repeat(self.min_integer_digits.saturating_sub(integer_part.len())) + integer_part;
+        if self.grouping {
+            let mut grouped = String::new();
+            for (i, ch) in integer_digits.chars().enumerate() {
+                if i != 0 && (integer_digits.len() - i) % 3 == 0 {
+                    grouped.push(',');
+                }
+                grouped.push(ch);
+            }
+            integer_digits = grouped;
+        }
+        let mut decimal_digits = decimal_part.to_string();
+        while decimal_digits.len() > self.min_decimal_places && decimal_digits.ends_with('0') {
+            decimal_digits.pop();
+        }
+        let mut result = format!("{}{}{}", sign, self.prefix, integer_digits);
+        if !decimal_digits.is_empty() {
+            result.push('.');
+            result.push_str(&decimal_digits);
+        }
+        result.push_str(unit);
+        result.push_str(&self.suffix);
+        result
+    }
+}
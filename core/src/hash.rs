@@ -0,0 +1,23 @@
+use crate::error::{IntErr, Interrupt, Never};
+use crate::interrupt::test_int;
+
+/// 64-bit DJB2 hash, e.g. `hello` => `210714636441`
+pub(crate) fn djb2<I: Interrupt>(s: &str, int: &I) -> Result<u64, IntErr<Never, I>> {
+    let mut hash: u64 = 5381;
+    for byte in s.bytes() {
+        test_int(int)?;
+        hash = hash.wrapping_mul(33).wrapping_add(u64::from(byte));
+    }
+    Ok(hash)
+}
+
+/// 64-bit FNV-1a hash, e.g. `hello` => `11831194018420276491`
+pub(crate) fn fnv1a<I: Interrupt>(s: &str, int: &I) -> Result<u64, IntErr<Never, I>> {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in s.bytes() {
+        test_int(int)?;
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    Ok(hash)
+}
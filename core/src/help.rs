@@ -0,0 +1,673 @@
+// identifier, usage, description
+type HelpEntry = (&'static str, &'static str, &'static str);
+
+const BUILT_IN_HELP: &[HelpEntry] = &[
+    (
+        "pi",
+        "pi",
+        "ratio of a circle's circumference to its diameter",
+    ),
+    ("\u{3c0}", "\u{3c0}", "alias for pi"),
+    (
+        "tau",
+        "tau",
+        "ratio of a circle's circumference to its radius",
+    ),
+    ("\u{3c4}", "\u{3c4}", "alias for tau"),
+    (
+        "e",
+        "e",
+        "Euler's number, the base of the natural logarithm",
+    ),
+    (
+        "i",
+        "i",
+        "the imaginary unit, equal to the square root of -1",
+    ),
+    ("sqrt", "sqrt(x)", "square root of x"),
+    ("cbrt", "cbrt(x)", "cube root of x"),
+    ("square", "square(x)", "x to the power of 2"),
+    ("cubic", "cubic(x)", "x to the power of 3"),
+    ("conjugate", "conjugate(z)", "complex conjugate of z"),
+    ("abs", "abs(x)", "absolute value of x"),
+    ("sin", "sin(x)", "sine of angle x (in radians)"),
+    ("cos", "cos(x)", "cosine of angle x (in radians)"),
+    ("tan", "tan(x)", "tangent of angle x (in radians)"),
+    ("asin", "asin(x)", "inverse sine of x, in radians"),
+    ("acos", "acos(x)", "inverse cosine of x, in radians"),
+    ("atan", "atan(x)", "inverse tangent of x, in radians"),
+    ("sinh", "sinh(x)", "hyperbolic sine of x"),
+    ("cosh", "cosh(x)", "hyperbolic cosine of x"),
+    ("tanh", "tanh(x)", "hyperbolic tangent of x"),
+    ("asinh", "asinh(x)", "inverse hyperbolic sine of x"),
+    ("acosh", "acosh(x)", "inverse hyperbolic cosine of x"),
+    ("atanh", "atanh(x)", "inverse hyperbolic tangent of x"),
+    ("cis", "cis(theta)", "cos theta + i * sin theta"),
+    ("ln", "ln(x)", "natural logarithm of x"),
+    ("log2", "log2(x)", "base 2 logarithm of x"),
+    ("log", "log(x)", "base 10 logarithm of x"),
+    ("log10", "log10(x)", "base 10 logarithm of x"),
+    ("exp", "exp(x)", "e to the power of x"),
+    ("approx.", "approx. x", "marks x as an approximate value"),
+    (
+        "approximately",
+        "approximately x",
+        "marks x as an approximate value",
+    ),
+    ("base", "base(n)", "number base n, e.g. '255 to base 16'"),
+    (
+        "differentiate",
+        "differentiate(f)",
+        "derivative of the function f",
+    ),
+    (
+        "series_e",
+        "series_e(n)",
+        "sum of the first n terms of the series 1/0! + 1/1! + 1/2! + ...",
+    ),
+    ("version", "version", "the current version of fend"),
+    (
+        "version_info",
+        "version_info",
+        "the major, minor and patch components of the current version",
+    ),
+    ("today", "today", "the current date"),
+    ("tomorrow", "tomorrow", "the date after today"),
+    ("yesterday", "yesterday", "the date before today"),
+    (
+        "earth",
+        "earth",
+        "properties of the Earth, e.g. 'mass of earth'",
+    ),
+    (
+        "moon",
+        "moon",
+        "properties of the Moon, e.g. 'radius of moon'",
+    ),
+    ("sun", "sun", "properties of the Sun, e.g. 'mass of sun'"),
+    ("mars", "mars", "properties of Mars, e.g. 'gravity of mars'"),
+    (
+        "venus",
+        "venus",
+        "properties of Venus, e.g. 'gravity of venus'",
+    ),
+    (
+        "jupiter",
+        "jupiter",
+        "properties of Jupiter, e.g. 'mass of jupiter'",
+    ),
+    (
+        "electron",
+        "electron",
+        "properties of the electron, e.g. 'mass of electron'",
+    ),
+    (
+        "proton",
+        "proton",
+        "properties of the proton, e.g. 'mass of proton'",
+    ),
+    (
+        "neutron",
+        "neutron",
+        "properties of the neutron, e.g. 'mass of neutron'",
+    ),
+    (
+        "to_fixed",
+        "to_fixed(x, n)",
+        "x as a string with exactly n decimal places",
+    ),
+    (
+        "to_precision",
+        "to_precision(x, n)",
+        "x as a string with exactly n significant figures",
+    ),
+    (
+        "num_digits",
+        "num_digits(n, base)",
+        "number of digits of the integer n when written in the given base",
+    ),
+    (
+        "reverse_digits",
+        "reverse_digits(n, base)",
+        "the integer n with its digits in the given base reversed",
+    ),
+    ("to_roman", "to_roman(n)", "the integer n as roman numerals"),
+    (
+        "from_roman",
+        "from_roman(s)",
+        "the value of the roman numerals in the string s",
+    ),
+    (
+        "bcd",
+        "bcd(n)",
+        "binary-coded decimal representation of the integer n",
+    ),
+    ("to_bcd", "to_bcd(n)", "alias for bcd"),
+    (
+        "from_bcd",
+        "from_bcd(n)",
+        "the integer represented by the binary-coded decimal number n",
+    ),
+    (
+        "interleave",
+        "interleave(a, b)",
+        "Morton code of a and b, i.e. their bits interleaved",
+    ),
+    (
+        "from_nato",
+        "from_nato(s)",
+        "the string spelled out in NATO phonetic alphabet words in s",
+    ),
+    ("from_NATO", "from_NATO(s)", "alias for from_nato"),
+    (
+        "hex_dump",
+        "hex_dump(s)",
+        "the UTF-8 bytes of the string s in hexadecimal",
+    ),
+    (
+        "hex_to_string",
+        "hex_to_string(s)",
+        "the string with the hexadecimal UTF-8 bytes in s",
+    ),
+    ("crc32", "crc32(s)", "CRC-32 checksum of the string s"),
+    ("crc16", "crc16(s)", "CRC-16/ARC checksum of the string s"),
+    ("djb2", "djb2(s)", "64-bit DJB2 hash of the string s"),
+    ("fnv1a", "fnv1a(s)", "64-bit FNV-1a hash of the string s"),
+    (
+        "unix_time",
+        "unix_time(date)",
+        "Unix timestamp (in seconds) of the start of the given date",
+    ),
+    (
+        "from_unix_time",
+        "from_unix_time(t)",
+        "the date of the Unix timestamp t (in seconds)",
+    ),
+    (
+        "lunar_phase",
+        "lunar_phase(date)",
+        "approximate phase of the moon on the given date",
+    ),
+    (
+        "season",
+        "season(date, hemisphere)",
+        "meteorological season of the date, in the northern hemisphere unless hemisphere is \"south\"",
+    ),
+    (
+        "time_zone_offset",
+        "time_zone_offset(name)",
+        "offset from UTC of a time zone abbreviation such as \"EST\", ignoring daylight saving time",
+    ),
+    (
+        "countdown",
+        "countdown(date)",
+        "number of days from today until the given date",
+    ),
+    (
+        "age",
+        "age(date)",
+        "years, months and days from the given date until today",
+    ),
+    (
+        "format_number",
+        "format_number(x, template)",
+        "x formatted using a template like \"#,##0.00\"",
+    ),
+    (
+        "nth",
+        "nth(n, list)",
+        "the n-th element of a list, starting at 1",
+    ),
+    ("add", "add(a, b)", "the sum of a and b"),
+    (
+        "zip",
+        "zip(f, a, b)",
+        "a list of f applied to each pair of elements from the lists a and b",
+    ),
+    (
+        "map",
+        "map(f, list)",
+        "a list of f applied to each element of the list",
+    ),
+    (
+        "filter",
+        "filter(pred, list)",
+        "the elements of the list for which pred is non-zero",
+    ),
+    (
+        "fold",
+        "fold(f, initial, list)",
+        "the result of combining the elements of the list with f, from left to right",
+    ),
+    ("reduce", "reduce(f, initial, list)", "alias for fold"),
+    (
+        "range",
+        "range(start, end, step)",
+        "a list of numbers from start up to (but not including) end, with an optional step",
+    ),
+    ("sum", "sum(list)", "the sum of all elements of the list"),
+    (
+        "tabulate",
+        "tabulate(f, start, end, step)",
+        "a table of the values of f from start up to and including end",
+    ),
+    (
+        "histogram",
+        "histogram(list, bins)",
+        "the number of elements in each of the given number of equal-width bins",
+    ),
+    ("mean", "mean(list)", "the arithmetic mean of the list"),
+    ("median", "median(list)", "the middle value of the sorted list"),
+    (
+        "variance",
+        "variance(list)",
+        "the population variance of the list (see also variance_sample)",
+    ),
+    ("variance_pop", "variance_pop(list)", "alias for variance"),
+    (
+        "variance_sample",
+        "variance_sample(list)",
+        "the sample variance of the list, which divides by n - 1 instead of n",
+    ),
+    (
+        "stddev",
+        "stddev(list)",
+        "the population standard deviation of the list (see also stddev_sample)",
+    ),
+    ("stddev_pop", "stddev_pop(list)", "alias for stddev"),
+    (
+        "stddev_sample",
+        "stddev_sample(list)",
+        "the sample standard deviation of the list",
+    ),
+    (
+        "format_with_uncertainty",
+        "format_with_uncertainty(x, uncertainty)",
+        "x and its uncertainty in \u{b1} notation, e.g. \"9.8 \u{b1} 0.1\"",
+    ),
+    ("bitcount", "bitcount(n)", "the number of one bits in n"),
+    ("popcount", "popcount(n)", "alias for bitcount"),
+    (
+        "clz",
+        "clz(n, width)",
+        "the number of leading zero bits in n, written with the given number of bits",
+    ),
+    (
+        "swap_bytes",
+        "swap_bytes(n, bytes)",
+        "reverses the byte order of n, which must be 1, 2, 4 or 8 bytes wide",
+    ),
+    (
+        "rotate_left",
+        "rotate_left(n, bits, width)",
+        "rotates the bits of a width-bit integer n to the left",
+    ),
+    (
+        "rotate_right",
+        "rotate_right(n, bits, width)",
+        "rotates the bits of a width-bit integer n to the right",
+    ),
+    ("gray_code", "gray_code(n)", "converts n to its Gray code"),
+    ("from_gray", "from_gray(n)", "converts a Gray code back to an integer"),
+    (
+        "hamming_distance",
+        "hamming_distance(a, b)",
+        "the number of bits that differ between a and b",
+    ),
+    (
+        "to_unary",
+        "to_unary(n)",
+        "the integer n as tally marks in groups of five",
+    ),
+    ("from_unary", "from_unary(s)", "counts the tally marks in s"),
+    (
+        "to_braille_number",
+        "to_braille_number(n)",
+        "the integer n written in Braille",
+    ),
+    (
+        "from_braille_number",
+        "from_braille_number(s)",
+        "parses a number written in Braille",
+    ),
+    (
+        "lcm_range",
+        "lcm_range(n)",
+        "the least common multiple of all integers from 1 to n",
+    ),
+    (
+        "primorial",
+        "primorial(n)",
+        "the product of all primes less than or equal to n",
+    ),
+    (
+        "collatz",
+        "collatz(n)",
+        "the number of steps the Collatz sequence starting at n takes to reach 1",
+    ),
+    (
+        "taxi_distance",
+        "taxi_distance(x1, y1, x2, y2)",
+        "the Manhattan distance between two points, i.e. |x2 - x1| + |y2 - y1|",
+    ),
+    (
+        "manhattan_distance",
+        "manhattan_distance(a, b)",
+        "alias for taxi_distance",
+    ),
+    (
+        "chebyshev_distance",
+        "chebyshev_distance(x1, y1, x2, y2)",
+        "the Chebyshev distance between two points, i.e. max(|x2 - x1|, |y2 - y1|)",
+    ),
+    (
+        "haversine",
+        "haversine(lat1, lon1, lat2, lon2)",
+        "the great-circle distance between two points on the earth (coordinates without a unit are in degrees)",
+    ),
+    (
+        "compass_bearing",
+        "compass_bearing(lat1, lon1, lat2, lon2)",
+        "the initial bearing from one point on the earth to another, in degrees from north (coordinates without a unit are in degrees)",
+    ),
+    (
+        "dB_to_power_ratio",
+        "dB_to_power_ratio(dB)",
+        "converts decibels to a power ratio, i.e. 10^(dB/10)",
+    ),
+    (
+        "power_ratio_to_dB",
+        "power_ratio_to_dB(ratio)",
+        "converts a power ratio to decibels, i.e. 10 log10(ratio)",
+    ),
+    (
+        "dB_to_amplitude_ratio",
+        "dB_to_amplitude_ratio(dB)",
+        "converts decibels to an amplitude ratio, i.e. 10^(dB/20)",
+    ),
+    (
+        "amplitude_ratio_to_dB",
+        "amplitude_ratio_to_dB(ratio)",
+        "converts an amplitude ratio to decibels, i.e. 20 log10(ratio)",
+    ),
+    (
+        "VSWR_to_reflection",
+        "VSWR_to_reflection(vswr)",
+        "converts a voltage standing wave ratio to a reflection coefficient",
+    ),
+    (
+        "reflection_to_VSWR",
+        "reflection_to_VSWR(gamma)",
+        "converts a reflection coefficient to a voltage standing wave ratio",
+    ),
+    (
+        "ohms_law",
+        "ohms_law(v, i, r)",
+        "solves v = i r for whichever of the voltage, current or resistance is given as ?",
+    ),
+    (
+        "parallel",
+        "parallel(a, b, ...)",
+        "the reciprocal of the sum of reciprocals, e.g. for resistors in parallel",
+    ),
+    (
+        "reciprocal_sum",
+        "reciprocal_sum(a, b, ...)",
+        "alias for parallel",
+    ),
+    (
+        "rc_time_constant",
+        "rc_time_constant(r, c)",
+        "the time constant r c of a resistor-capacitor circuit",
+    ),
+    (
+        "lc_resonance",
+        "lc_resonance(l, c)",
+        "the resonant frequency 1 / (2 pi sqrt(l c)) of an inductor-capacitor circuit",
+    ),
+    (
+        "snell",
+        "snell(n1, theta1, n2)",
+        "the angle of refraction when light passes from refractive index n1 to n2",
+    ),
+    (
+        "ideal_gas",
+        "ideal_gas(p, v, n, t)",
+        "solves p v = n R t for whichever of the pressure, volume, amount or temperature is given as ?",
+    ),
+    (
+        "ordinal_words",
+        "ordinal_words(n)",
+        "the integer n spelled out as an ordinal, e.g. twenty-first",
+    ),
+    (
+        "memo",
+        "memo(f)",
+        "the function f, remembering its result for each argument",
+    ),
+    (
+        "integer_part",
+        "integer_part(x)",
+        "x rounded towards zero, keeping its unit",
+    ),
+    (
+        "fractional_part",
+        "fractional_part(x)",
+        "x minus its integer part, keeping its unit",
+    ),
+    (
+        "to_balanced_ternary",
+        "to_balanced_ternary(n)",
+        "the integer n in balanced ternary, using the digits 1, 0 and T (-1)",
+    ),
+    (
+        "is_leap_year",
+        "is_leap_year(year)",
+        "1 if the year is a leap year in the Gregorian calendar, otherwise 0",
+    ),
+    (
+        "days_in_year",
+        "days_in_year(year)",
+        "the number of days in the year, i.e. 365 or 366",
+    ),
+    (
+        "easter",
+        "easter(year)",
+        "the date of (Western) Easter Sunday in the given year",
+    ),
+    ("easter_western", "easter_western(year)", "alias for easter"),
+    (
+        "easter_orthodox",
+        "easter_orthodox(year)",
+        "the date of Orthodox Easter Sunday in the given year",
+    ),
+    (
+        "convert_currency",
+        "convert_currency(amount, currency, rate)",
+        "converts an amount of money to another currency using the given exchange rate",
+    ),
+    (
+        "exponent",
+        "exponent(x, base)",
+        "the exponent of x in scientific notation, e.g. 3 for 1234 in base 10",
+    ),
+    (
+        "mantissa",
+        "mantissa(x, base)",
+        "the mantissa of x in scientific notation, e.g. 1.234 for 1234 in base 10",
+    ),
+    (
+        "apply_at",
+        "apply_at(f, n, x)",
+        "applies the function f to x n times, e.g. f(f(f(x))) for n = 3",
+    ),
+    (
+        "compose",
+        "compose(f, g)",
+        "the function that applies g and then f, also written as f \u{2218} g",
+    ),
+    (
+        "convergents",
+        "convergents(x, n)",
+        "the first n convergents of the continued fraction of x, e.g. 3/1, 22/7, 333/106 for pi",
+    ),
+    (
+        "bernstein",
+        "bernstein(n, k, t)",
+        "the Bernstein basis polynomial C(n, k) * t^k * (1 - t)^(n - k) for t between 0 and 1",
+    ),
+    (
+        "?",
+        "?",
+        "placeholder for the unknown argument of ohms_law and ideal_gas",
+    ),
+    (
+        "dump_scope",
+        "dump_scope",
+        "an object containing all variables that are currently in scope",
+    ),
+    (
+        "auto",
+        "x to auto",
+        "x formatted exactly if possible, otherwise to 10 decimal places (the default)",
+    ),
+    (
+        "exact",
+        "x to exact",
+        "x formatted exactly, e.g. as a fraction or with recurring digits",
+    ),
+    (
+        "float",
+        "x to float",
+        "x formatted as a decimal number, with any recurring digits in parentheses",
+    ),
+    ("fraction", "x to fraction", "x formatted as an improper fraction"),
+    ("frac", "x to frac", "alias for fraction"),
+    (
+        "mixed_fraction",
+        "x to mixed_fraction",
+        "x formatted as a mixed fraction, e.g. 1 1/2",
+    ),
+    (
+        "ratio",
+        "x to ratio",
+        "x formatted as a ratio, including integers, e.g. 2/1",
+    ),
+    ("dp", "x to n dp", "x formatted with n decimal places"),
+    ("sf", "x to n sf", "x formatted with n significant figures"),
+    (
+        "currency",
+        "x to currency",
+        "x formatted as an amount of money with two decimal places and grouped thousands",
+    ),
+    (
+        "dms",
+        "x to dms",
+        "an angle formatted in degrees, minutes and seconds, e.g. 1\u{b0}30\u{2032}0\u{2033}",
+    ),
+    (
+        "hms",
+        "x to hms",
+        "a duration formatted in hours, minutes and seconds, e.g. 1h 1m 1.5s",
+    ),
+    (
+        "clock",
+        "x to clock",
+        "a duration formatted as a time on a 24-hour clock, e.g. 13:01:01",
+    ),
+    ("clock_24h", "x to clock_24h", "alias for clock"),
+    (
+        "clock_12h",
+        "x to clock_12h",
+        "a duration formatted as a time on a 12-hour clock, e.g. 01:01 PM",
+    ),
+    (
+        "engineering_prefixed",
+        "x to engineering_prefixed",
+        "x formatted in engineering notation using SI prefixes, e.g. 1.234 km",
+    ),
+    ("decimal", "x to decimal", "x formatted in base 10"),
+    ("dec", "x to dec", "alias for decimal"),
+    ("hexadecimal", "x to hexadecimal", "x formatted in base 16"),
+    ("hex", "x to hex", "alias for hexadecimal"),
+    ("octal", "x to octal", "x formatted in base 8"),
+    ("oct", "x to oct", "alias for octal"),
+    ("binary", "x to binary", "x formatted in base 2"),
+    (
+        "help",
+        "help(name)",
+        "description of a built-in function, constant or unit",
+    ),
+];
+
+fn strip_definition_prefixes(mut definition: &str) -> &str {
+    for prefix in &["lp@", "l@", "sp@", "s@"] {
+        if let Some(remaining) = definition.strip_prefix(prefix) {
+            definition = remaining;
+            break;
+        }
+    }
+    definition.strip_prefix('=').unwrap_or(definition)
+}
+
+/// Returns a short description of the given built-in function, constant
+/// or unit, or an error if no such identifier exists.
+pub(crate) fn get_help(name: &str) -> Result<String, String> {
+    for (ident, usage, description) in BUILT_IN_HELP {
+        if *ident == name {
+            return Ok(format!("{}: {}", usage, description));
+        }
+    }
+    if let Some((singular, definition, description)) = crate::units::query_description(name) {
+        if !description.is_empty() {
+            return Ok(format!("{}: {}", singular, description));
+        }
+        let definition = strip_definition_prefixes(definition);
+        return Ok(if definition == "!" {
+            format!("{}: base unit", singular)
+        } else {
+            format!("{}: defined as {}", singular, definition)
+        });
+    }
+    Err(format!("no help available for '{}'", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // every identifier that `resolve_identifier` handles itself, e.g.
+    // `"sin"` or `"log" | "log10"`
+    fn built_in_identifiers() -> Vec<&'static str> {
+        let source = include_str!("ast.rs");
+        let start = source
+            .find("pub(crate) fn resolve_identifier")
+            .expect("resolve_identifier not found");
+        let end = start + source[start..].find("\n}\n").expect("end of function");
+        let mut identifiers = vec![];
+        for line in source[start..end].lines() {
+            let line = line.trim_start();
+            if !line.starts_with('"') {
+                continue;
+            }
+            let pattern = line.split("=>").next().unwrap_or("");
+            for ident in pattern.split('|') {
+                let ident = ident.trim().trim_matches('"');
+                // skip escaped identifiers like "\u{3c0}"
+                if !ident.is_empty() && !ident.contains('\\') {
+                    identifiers.push(ident);
+                }
+            }
+        }
+        identifiers
+    }
+
+    #[test]
+    fn all_built_in_identifiers_have_help() {
+        let identifiers = built_in_identifiers();
+        assert!(identifiers.len() > 100);
+        for ident in identifiers {
+            assert!(get_help(ident).is_ok(), "no help available for '{}'", ident);
+        }
+    }
+}
@@ -1,4 +1,7 @@
 use crate::error::IntErr;
+use std::cell::Cell;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
 
 pub trait Interrupt {
     fn should_interrupt(&self) -> bool;
@@ -32,3 +35,78 @@ impl Interrupt for Never {
         false
     }
 }
+
+/// An interrupt that stops evaluation once a given amount of time has
+/// passed since it was created.
+///
+/// This relies on `std::time::Instant`, which panics on
+/// `wasm32-unknown-unknown`, so it isn't available on `wasm32` targets.
+/// Implement `Interrupt` with a platform-specific clock there instead.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct Timeout {
+    start: Instant,
+    duration: Duration,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Timeout {
+    /// Creates a timeout that expires after the given duration
+    #[must_use]
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            start: Instant::now(),
+            duration,
+        }
+    }
+
+    /// Returns how much time is left before the timeout expires, or
+    /// `None` if it has already expired
+    #[must_use]
+    pub fn remaining(&self) -> Option<Duration> {
+        self.duration.checked_sub(self.start.elapsed())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Interrupt for Timeout {
+    fn should_interrupt(&self) -> bool {
+        self.remaining().is_none()
+    }
+}
+
+/// An interrupt that stops evaluation after a fixed number of evaluation
+/// steps. Unlike a `Timeout`, this doesn't depend on how fast the computer
+/// is, so an interrupted calculation is always interrupted at the same point.
+#[derive(Debug)]
+pub struct MaxIterationsInterrupt {
+    remaining: Cell<u64>,
+}
+
+impl MaxIterationsInterrupt {
+    /// Creates an interrupt that allows at most `n` evaluation steps
+    #[must_use]
+    pub fn new(n: u64) -> Self {
+        Self {
+            remaining: Cell::new(n),
+        }
+    }
+
+    /// Returns how many evaluation steps are left
+    #[must_use]
+    pub fn remaining(&self) -> u64 {
+        self.remaining.get()
+    }
+}
+
+impl Interrupt for MaxIterationsInterrupt {
+    fn should_interrupt(&self) -> bool {
+        match self.remaining.get().checked_sub(1) {
+            Some(remaining) => {
+                self.remaining.set(remaining);
+                false
+            }
+            None => true,
+        }
+    }
+}
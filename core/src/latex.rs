@@ -0,0 +1,126 @@
+use crate::{Span, SpanKind};
+
+fn escape(s: &str) -> String {
+    let mut result = String::new();
+    for ch in s.chars() {
+        match ch {
+            '\\' => result.push_str("\\backslash{}"),
+            '~' => result.push_str("\\sim{}"),
+            '^' => result.push_str("\\hat{}"),
+            '#' | '$' | '%' | '&' | '_' | '{' | '}' => {
+                result.push('\\');
+                result.push(ch);
+            }
+            _ => result.push(ch),
+        }
+    }
+    result
+}
+
+fn text(s: &str) -> String {
+    let mut result = String::new();
+    for ch in s.chars() {
+        match ch {
+            '\\' => result.push_str("\\textbackslash{}"),
+            '~' => result.push_str("\\textasciitilde{}"),
+            '^' => result.push_str("\\textasciicircum{}"),
+            '#' | '$' | '%' | '&' | '_' | '{' | '}' => {
+                result.push('\\');
+                result.push(ch);
+            }
+            _ => result.push(ch),
+        }
+    }
+    format!("\\text{{{}}}", result)
+}
+
+// converts recurring digits like `0.(3)` to `0.\overline{3}`, and `π` to `\pi`
+fn plain_number(s: &str) -> String {
+    let mut result = String::new();
+    for ch in s.chars() {
+        match ch {
+            '(' => result.push_str("\\overline{"),
+            ')' => result.push('}'),
+            '\u{3c0}' => result.push_str("\\pi "),
+            _ => result.push_str(&escape(&ch.to_string())),
+        }
+    }
+    result.trim_end().to_string()
+}
+
+// formats a number such as `1 1/3`, `2π/3` or `1 + 2i`
+fn number(s: &str) -> String {
+    let mut result = String::new();
+    for (i, word) in s.split(' ').enumerate() {
+        // mixed fractions like `1 1/3` are written without a space
+        if i != 0 && !(word.contains('/') && result.ends_with(char::is_numeric)) {
+            result.push(' ');
+        }
+        match word.find('/') {
+            Some(idx) => {
+                result.push_str("\\frac{");
+                result.push_str(&plain_number(&word[..idx]));
+                result.push_str("}{");
+                result.push_str(&plain_number(&word[idx + 1..]));
+                result.push('}');
+            }
+            None => result.push_str(&plain_number(word)),
+        }
+    }
+    result
+}
+
+// formats units such as ` kg m^2 / s` as `\,\mathrm{kg}\,\mathrm{m}^{2}/\mathrm{s}`
+fn units(s: &str) -> String {
+    let mut result = String::new();
+    let mut separator = s.starts_with(' ');
+    for word in s.split(' ').filter(|word| !word.is_empty()) {
+        if word == "/" {
+            result.push('/');
+            separator = false;
+            continue;
+        }
+        if separator {
+            result.push_str("\\,");
+        }
+        separator = true;
+        let (unit, exponent) = match word.find('^') {
+            Some(idx) => (&word[..idx], Some(&word[idx + 1..])),
+            None => (word, None),
+        };
+        result.push_str("\\mathrm{");
+        result.push_str(&escape(unit));
+        result.push('}');
+        if let Some(exponent) = exponent {
+            let exponent = exponent.trim_start_matches('(').trim_end_matches(')');
+            result.push_str("^{");
+            result.push_str(&number(exponent));
+            result.push('}');
+        }
+    }
+    result
+}
+
+/// Converts the spans of a result to LaTeX math markup. Returns `None`
+/// for results that are not mathematical values, such as functions.
+pub(crate) fn spans_to_latex(spans: &[Span]) -> Option<String> {
+    let mut result = String::new();
+    for span in spans {
+        match span.kind {
+            SpanKind::Number => result.push_str(&number(&span.string)),
+            SpanKind::Ident if span.string == "approx. " => result.push_str("\\approx "),
+            SpanKind::Ident => result.push_str(&units(&span.string)),
+            SpanKind::String | SpanKind::Date => result.push_str(&text(&span.string)),
+            SpanKind::Whitespace => result.push_str("\\ "),
+            SpanKind::Operator => result.push_str(&span.string.replace('\u{b1}', "\\pm")),
+            SpanKind::BuiltInFunction | SpanKind::Keyword | SpanKind::Other | SpanKind::Error => {
+                return None
+            }
+        }
+    }
+    if result.is_empty() {
+        None
+    } else {
+        Some(result)
+    }
+}
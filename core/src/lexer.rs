@@ -29,6 +29,17 @@ pub(crate) enum Symbol {
     Of,
     ShiftLeft,
     ShiftRight,
+    Comma,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+    PlusMinus,
+    Compose,
+    And,
+    Or,
 }
 
 pub(crate) enum Error {
@@ -131,6 +142,17 @@ impl fmt::Display for Symbol {
             Self::Of => "of",
             Self::ShiftLeft => "<<",
             Self::ShiftRight => ">>",
+            Self::Comma => ",",
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::Greater => ">",
+            Self::GreaterOrEqual => ">=",
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+            Self::PlusMinus => "\u{b1}",
+            Self::Compose => "\u{2218}",
+            Self::And => "and",
+            Self::Or => "or",
         };
         write!(f, "{}", s)?;
         Ok(())
@@ -173,6 +195,18 @@ fn parse_digit_separator(input: &str) -> Result<((), &str), Error> {
     }
 }
 
+// Checks whether the input starts with exactly three digits, e.g. `234`
+// in `1,234`
+fn is_thousands_group(mut input: &str, base: Base) -> bool {
+    for _ in 0..3 {
+        match parse_ascii_digit(input, base) {
+            Ok((_, remaining)) => input = remaining,
+            Err(_) => return false,
+        }
+    }
+    parse_ascii_digit(input, base).is_err()
+}
+
 // Parses a plain integer with no whitespace and no base prefix.
 // Leading minus sign is not allowed.
 fn parse_integer<'a, E: From<Error>>(
@@ -185,6 +219,14 @@ fn parse_integer<'a, E: From<Error>>(
     process_digit(digit)?;
     let mut parsed_digit_separator;
     loop {
+        if let Some(remaining) = input.strip_prefix(',') {
+            // a comma is only a thousands separator if it is followed by
+            // exactly three digits, otherwise it separates list items
+            // or arguments, e.g. in `max(1,2)`
+            if !allow_digit_separator || !is_thousands_group(remaining, base) {
+                break;
+            }
+        }
         if let Ok((_, remaining)) = parse_digit_separator(input) {
             input = remaining;
             parsed_digit_separator = true;
@@ -426,17 +468,17 @@ fn parse_number<'a, I: Interrupt>(
 
 fn is_valid_in_ident(ch: char, prev: Option<char>) -> bool {
     let allowed_chars = [
-        ',', '&', '_', '⅛', '¼', '⅜', '½', '⅝', '¾', '⅞', '⅙', '⅓', '⅔', '⅚', '⅕', '⅖', '⅗', '⅘',
-        '°', '$', '℃', '℉', '℧', '℈', '℥', '℔', '¢', '£', '¥', '€', '₩', '₪', '₤', '₨', '฿', '₡',
-        '₣', '₦', '₧', '₫', '₭', '₮', '₯', '₱', '﷼', '﹩', '￠', '￡', '￥', '￦', '㍱', '㍲',
-        '㍳', '㍴', '㍶', '㎀', '㎁', '㎂', '㎃', '㎄', '㎅', '㎆', '㎇', '㎈', '㎉', '㎊', '㎋',
-        '㎌', '㎍', '㎎', '㎏', '㎐', '㎑', '㎒', '㎓', '㎔', '㎕', '㎖', '㎗', '㎘', '㎙', '㎚',
-        '㎛', '㎜', '㎝', '㎞', '㎟', '㎠', '㎡', '㎢', '㎣', '㎤', '㎥', '㎦', '㎧', '㎨', '㎩',
-        '㎪', '㎫', '㎬', '㎭', '㎮', '㎯', '㎰', '㎱', '㎲', '㎳', '㎴', '㎵', '㎶', '㎷', '㎸',
-        '㎹', '㎺', '㎻', '㎼', '㎽', '㎾', '㎿', '㏀', '㏁', '㏃', '㏄', '㏅', '㏆', '㏈', '㏉',
-        '㏊', '㏌', '㏏', '㏐', '㏓', '㏔', '㏕', '㏖', '㏗', '㏙', '㏛', '㏜', '㏝',
+        '&', '_', '⅛', '¼', '⅜', '½', '⅝', '¾', '⅞', '⅙', '⅓', '⅔', '⅚', '⅕', '⅖', '⅗', '⅘', '°',
+        '$', '℃', '℉', '℧', '℈', '℥', '℔', '¢', '£', '¥', '€', '₩', '₪', '₤', '₨', '฿', '₡', '₣',
+        '₦', '₧', '₫', '₭', '₮', '₯', '₱', '﷼', '﹩', '￠', '￡', '￥', '￦', '㍱', '㍲', '㍳',
+        '㍴', '㍶', '㎀', '㎁', '㎂', '㎃', '㎄', '㎅', '㎆', '㎇', '㎈', '㎉', '㎊', '㎋', '㎌',
+        '㎍', '㎎', '㎏', '㎐', '㎑', '㎒', '㎓', '㎔', '㎕', '㎖', '㎗', '㎘', '㎙', '㎚', '㎛',
+        '㎜', '㎝', '㎞', '㎟', '㎠', '㎡', '㎢', '㎣', '㎤', '㎥', '㎦', '㎧', '㎨', '㎩', '㎪',
+        '㎫', '㎬', '㎭', '㎮', '㎯', '㎰', '㎱', '㎲', '㎳', '㎴', '㎵', '㎶', '㎷', '㎸', '㎹',
+        '㎺', '㎻', '㎼', '㎽', '㎾', '㎿', '㏀', '㏁', '㏃', '㏄', '㏅', '㏆', '㏈', '㏉', '㏊',
+        '㏌', '㏏', '㏐', '㏓', '㏔', '㏕', '㏖', '㏗', '㏙', '㏛', '㏜', '㏝',
     ];
-    let only_valid_by_themselves = ['%', '‰', '‱', '′', '″', '’', '”', 'π'];
+    let only_valid_by_themselves = ['%', '‰', '‱', '′', '″', '’', '”', 'π', '?'];
     if only_valid_by_themselves.contains(&ch) {
         // these are only valid if there was no previous char
         prev.is_none()
@@ -444,6 +486,9 @@ fn is_valid_in_ident(ch: char, prev: Option<char>) -> bool {
         // if prev was a char that's only valid by itself, then this next
         // char cannot be part of an identifier
         false
+    } else if prev == Some('°') && ch.is_ascii_digit() {
+        // allows angles like `1°30′` to be split into degrees and minutes
+        false
     } else if ch.is_alphabetic() || allowed_chars.contains(&ch) {
         true
     } else {
@@ -474,6 +519,8 @@ fn parse_ident(input: &str, allow_dots: bool) -> Result<(Token<'_>, &str), Error
             "to" | "as" | "in" => Token::Symbol(Symbol::ArrowConversion),
             "per" => Token::Symbol(Symbol::Div),
             "of" => Token::Symbol(Symbol::Of),
+            "and" => Token::Symbol(Symbol::And),
+            "or" => Token::Symbol(Symbol::Or),
             _ => Token::Ident(Ident::new(ident)),
         },
         input,
@@ -493,8 +540,17 @@ fn parse_symbol<'a>(ch: char, input: &mut &'a str) -> Result<Token<'a>, Error> {
     Ok(Token::Symbol(match ch {
         '(' => Symbol::OpenParens,
         ')' => Symbol::CloseParens,
+        ',' => Symbol::Comma,
         '+' => Symbol::Add,
-        '!' => Symbol::Factorial,
+        '\u{b1}' => Symbol::PlusMinus,
+        '\u{2218}' => Symbol::Compose,
+        '!' => {
+            if test_next('=') {
+                Symbol::NotEqual
+            } else {
+                Symbol::Factorial
+            }
+        }
         '-' => {
             if test_next('>') {
                 Symbol::ArrowConversion
@@ -515,6 +571,8 @@ fn parse_symbol<'a>(ch: char, input: &mut &'a str) -> Result<Token<'a>, Error> {
         '=' => {
             if test_next('>') {
                 Symbol::Fn
+            } else if test_next('=') {
+                Symbol::Equal
             } else {
                 return Err(Error::UnexpectedChar(ch));
             }
@@ -524,15 +582,19 @@ fn parse_symbol<'a>(ch: char, input: &mut &'a str) -> Result<Token<'a>, Error> {
         '<' => {
             if test_next('<') {
                 Symbol::ShiftLeft
+            } else if test_next('=') {
+                Symbol::LessOrEqual
             } else {
-                return Err(Error::UnexpectedChar(ch));
+                Symbol::Less
             }
         }
         '>' => {
             if test_next('>') {
                 Symbol::ShiftRight
+            } else if test_next('=') {
+                Symbol::GreaterOrEqual
             } else {
-                return Err(Error::UnexpectedChar(ch));
+                Symbol::Greater
             }
         }
         _ => return Err(Error::UnexpectedChar(ch)),
@@ -681,15 +743,19 @@ pub(crate) struct Lexer<'a, 'b, I: Interrupt> {
 impl<'a, 'b, I: Interrupt> Lexer<'a, 'b, I> {
     fn next_token(&mut self) -> Result<Option<Token<'a>>, IntErr<Error, I>> {
         while let Some(ch) = self.input.chars().next() {
-            if self.input.starts_with("# ") {
-                let (_, remaining) = self.input.split_at(2);
+            let mut chars = self.input.chars();
+            let is_comment = match (chars.next(), chars.next()) {
+                (Some('#'), None) => true,
+                (Some('#'), Some(next)) => next.is_whitespace(),
+                _ => false,
+            };
+            if is_comment {
+                // comments extend until the end of the line, and are
+                // treated like whitespace
+                let comment_length = self.input.find('\n').unwrap_or(self.input.len());
+                let (_, remaining) = self.input.split_at(comment_length);
                 self.input = remaining;
-                if let Some(idx) = self.input.find('\n') {
-                    let (_, remaining2) = self.input.split_at(idx);
-                    self.input = remaining2;
-                } else {
-                    return Ok(None);
-                }
+                continue;
             }
             if !ch.is_whitespace() {
                 break;
@@ -9,26 +9,43 @@
 #![doc(html_root_url = "https://docs.rs/fend-core/0.1.14")]
 
 mod ast;
+mod balanced;
+mod bits;
+mod braille;
+mod checksum;
 mod date;
 mod error;
 mod eval;
 mod format;
+mod hash;
+mod help;
 mod ident;
 mod interrupt;
+mod latex;
 mod lexer;
+mod markdown;
+mod nato;
 mod num;
 mod parser;
+mod roman;
 mod scope;
+mod timezones;
+mod unary;
 mod units;
 mod value;
 
-pub use interrupt::Interrupt;
+#[cfg(not(target_arch = "wasm32"))]
+pub use interrupt::Timeout;
+pub use interrupt::{Interrupt, MaxIterationsInterrupt};
+pub use units::ExchangeRateProvider;
 
 /// This contains the result of a computation.
 #[derive(PartialEq, Eq, Debug)]
 pub struct FendResult {
     plain_result: String,
     span_result: Vec<Span>,
+    is_truncated: bool,
+    rational_approximation: eval::RationalApproximation,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -42,6 +59,11 @@ pub enum SpanKind {
     Whitespace,
     Ident,
     Other,
+    /// An operator such as the `±` between a value and its uncertainty
+    Operator,
+    /// Part of the input that could not be evaluated, see
+    /// `Context::set_partial_evaluation()`
+    Error,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -91,6 +113,43 @@ impl FendResult {
         })
     }
 
+    /// Converts the result to LaTeX math markup, e.g. `\frac{1}{3}` for
+    /// the fraction `1/3`. Returns `None` if the result cannot be
+    /// represented in LaTeX, e.g. because it is a function.
+    #[must_use]
+    pub fn to_latex(&self) -> Option<String> {
+        latex::spans_to_latex(&self.span_result)
+    }
+
+    /// Converts the result to Markdown, e.g. `≈ **3.1415926535**` for `pi`
+    /// or `**3** m²` for `3 m^2`. Numbers are shown in bold, and the result
+    /// is escaped so it can be pasted into documentation or chat messages.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        markdown::spans_to_markdown(&self.span_result)
+    }
+
+    /// Returns the numerator and denominator of the result if it is a
+    /// rational number, e.g. `("1", "3")` for `1/3`. Approximate results
+    /// return the fraction that was used to calculate them. This returns
+    /// `None` for irrational values like `pi`, as well as for complex numbers
+    /// and values that aren't numbers.
+    #[must_use]
+    pub fn get_rational_approximation(&self) -> Option<(String, String)> {
+        self.rational_approximation
+            .clone()?
+            .numerator_and_denominator(&interrupt::Never::default())
+            .ok()
+            .flatten()
+    }
+
+    /// Returns whether the result was shortened because it exceeded the
+    /// maximum output length set with `Context::set_max_output_length()`.
+    #[must_use]
+    pub fn is_truncated(&self) -> bool {
+        self.is_truncated
+    }
+
     /// This used to retrieve a list of other results of the computation,
     /// but now returns an empty iterator. This method is deprecated and
     /// may be removed in a future release.
@@ -112,6 +171,10 @@ struct CurrentTimeInfo {
 #[derive(Clone)]
 pub struct Context {
     current_time: Option<CurrentTimeInfo>,
+    max_output_length: Option<usize>,
+    partial_evaluation: bool,
+    unit_cache: units::UnitCache,
+    exchange_rate_provider: Option<std::sync::Arc<dyn ExchangeRateProvider>>,
 }
 
 impl Default for Context {
@@ -123,9 +186,25 @@ impl Default for Context {
 impl Context {
     /// Create a new context instance. This can be fairly slow, and should
     /// only be done once if possible.
+    ///
+    /// Every new context starts out in the same state, so calling this
+    /// multiple times is equivalent to resetting the context.
     #[must_use]
     pub fn new() -> Self {
-        Self { current_time: None }
+        Self {
+            current_time: None,
+            max_output_length: None,
+            partial_evaluation: false,
+            unit_cache: units::UnitCache::new(),
+            exchange_rate_provider: None,
+        }
+    }
+
+    /// Reset this context to the state returned by `Context::new()`,
+    /// discarding any configuration such as the current time. This is
+    /// useful for REPL commands that clear the session.
+    pub fn reset(&mut self) {
+        *self = Self::new();
     }
 
     /// Set the current time. This API will likely change in the future!
@@ -142,6 +221,105 @@ impl Context {
             timezone_offset_secs: tz_offset_secs,
         });
     }
+
+    /// Limit results to the given number of characters. Longer results
+    /// are cut off and end with `...`, which is useful for very large
+    /// numbers like `1000!`. Only numbers and strings are cut off in the
+    /// middle, other parts of the result such as units are either kept or
+    /// removed entirely. By default the output length is unlimited.
+    pub fn set_max_output_length(&mut self, chars: usize) {
+        self.max_output_length = Some(chars);
+    }
+
+    /// Remove the limit set with `set_max_output_length()`.
+    pub fn clear_max_output_length(&mut self) {
+        self.max_output_length = None;
+    }
+
+    /// Enable or disable partial evaluation. If a sum like
+    /// `1 + unknown_unit + 2` can't be evaluated, the terms that can be
+    /// evaluated are added up instead, and the others are returned as
+    /// `SpanKind::Error` spans (e.g. `3 + unknown_unit`). This is useful
+    /// for highlighting errors in an editor. It is disabled by default.
+    pub fn set_partial_evaluation(&mut self, enabled: bool) {
+        self.partial_evaluation = enabled;
+    }
+
+    /// Use the given exchange rates instead of fend's built-in ones, which
+    /// may be out of date. Each currency's rate is requested when it is
+    /// first used, and then kept until the provider is set again or the
+    /// context is reset. Setting the same provider again can therefore be
+    /// used to refresh the rates.
+    pub fn set_exchange_rate_provider(&mut self, provider: impl ExchangeRateProvider + 'static) {
+        self.exchange_rate_provider = Some(std::sync::Arc::new(provider));
+        units::clear_cached_currencies(&mut self.unit_cache);
+    }
+
+    /// Evaluate with a time limit, e.g.
+    /// `context.with_timeout(Duration::from_millis(500)).evaluate("1+1")`.
+    /// The timeout starts as soon as this method is called. This is not
+    /// available on `wasm32` targets, see `Timeout`.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[must_use]
+    pub fn with_timeout(&mut self, duration: std::time::Duration) -> ContextWithTimeout<'_> {
+        ContextWithTimeout {
+            context: self,
+            timeout: Timeout::new(duration),
+        }
+    }
+}
+
+/// A context combined with a `Timeout`, created by `Context::with_timeout()`.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct ContextWithTimeout<'a> {
+    context: &'a mut Context,
+    timeout: Timeout,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ContextWithTimeout<'_> {
+    /// Evaluates the input, returning the error `"interrupted"` if the
+    /// timeout expires first. See `evaluate_with_interrupt()`.
+    ///
+    /// # Errors
+    /// It returns an error if the given string is invalid, or if the
+    /// evaluation takes too long.
+    pub fn evaluate(&mut self, input: &str) -> Result<FendResult, String> {
+        evaluate_with_interrupt(input, self.context, &self.timeout)
+    }
+}
+
+// Shortens the spans to at most `max_length` characters (plus a trailing
+// ellipsis), returning whether anything was removed.
+fn truncate_spans(spans: &mut Vec<Span>, max_length: usize) -> bool {
+    let mut remaining = max_length;
+    for (i, span) in spans.iter_mut().enumerate() {
+        let length = span.string.chars().count();
+        if length <= remaining {
+            remaining -= length;
+            continue;
+        }
+        let mut kept_spans = i;
+        if let SpanKind::Number | SpanKind::String = span.kind {
+            if let Some((idx, _)) = span.string.char_indices().nth(remaining) {
+                span.string.truncate(idx);
+            }
+            if !span.string.is_empty() {
+                kept_spans += 1;
+            }
+        }
+        spans.truncate(kept_spans);
+        while let Some(Span {
+            kind: SpanKind::Whitespace,
+            ..
+        }) = spans.last()
+        {
+            spans.pop();
+        }
+        spans.push(Span::from_string("...".to_string()));
+        return true;
+    }
+    false
 }
 
 /// This function evaluates a string using the given context. Any evaluation using this
@@ -174,13 +352,20 @@ pub fn evaluate_with_interrupt(
         return Ok(FendResult {
             plain_result: String::new(),
             span_result: vec![],
+            is_truncated: false,
+            rational_approximation: None,
         });
     }
-    let result = match eval::evaluate_to_spans(input, None, context, int) {
-        Ok(value) => value,
-        // TODO: handle different interrupt values
-        Err(error::IntErr::Interrupt(_)) => return Err("interrupted".to_string()),
-        Err(error::IntErr::Error(e)) => return Err(e),
+    let (mut result, rational_approximation) =
+        match eval::evaluate_to_spans(input, None, context, int) {
+            Ok(value) => value,
+            // TODO: handle different interrupt values
+            Err(error::IntErr::Interrupt(_)) => return Err("interrupted".to_string()),
+            Err(error::IntErr::Error(e)) => return Err(e),
+        };
+    let is_truncated = match context.max_output_length {
+        Some(max_length) => truncate_spans(&mut result, max_length),
+        None => false,
     };
     let mut plain_result = String::new();
     for s in &result {
@@ -189,6 +374,8 @@ pub fn evaluate_with_interrupt(
     Ok(FendResult {
         plain_result,
         span_result: result,
+        is_truncated,
+        rational_approximation,
     })
 }
 
@@ -196,6 +383,30 @@ const fn get_version_as_str() -> &'static str {
     "0.1.14"
 }
 
+// Parses a version number at compile time. Cargo guarantees that the
+// version components only contain digits.
+const fn parse_version_number(number: &str) -> u64 {
+    let digits = number.as_bytes();
+    let mut result = 0;
+    let mut i = 0;
+    while i < digits.len() {
+        result = result * 10 + (digits[i] - b'0') as u64;
+        i += 1;
+    }
+    result
+}
+
+/// The major, minor and patch numbers of the version, followed by the
+/// pre-release identifier (which is empty for regular releases).
+const fn get_version_components() -> (u64, u64, u64, &'static str) {
+    (
+        parse_version_number(env!("CARGO_PKG_VERSION_MAJOR")),
+        parse_version_number(env!("CARGO_PKG_VERSION_MINOR")),
+        parse_version_number(env!("CARGO_PKG_VERSION_PATCH")),
+        env!("CARGO_PKG_VERSION_PRE"),
+    )
+}
+
 /// Returns the current version of `fend-core`.
 #[must_use]
 pub fn get_version() -> String {
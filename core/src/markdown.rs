@@ -0,0 +1,74 @@
+use crate::{Span, SpanKind};
+
+fn escape(s: &str) -> String {
+    let mut result = String::new();
+    for ch in s.chars() {
+        if matches!(
+            ch,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '~'
+        ) {
+            result.push('\\');
+        }
+        result.push(ch);
+    }
+    result
+}
+
+fn superscript(ch: char) -> Option<char> {
+    Some(match ch {
+        '0' => '\u{2070}',
+        '1' => '\u{b9}',
+        '2' => '\u{b2}',
+        '3' => '\u{b3}',
+        '4'..='9' => std::char::from_u32(0x2070 + u32::from(ch) - u32::from('0'))?,
+        '-' => '\u{207b}',
+        _ => return None,
+    })
+}
+
+// formats units such as ` kg m^2 / s` as ` kg m² / s`, keeping exponents
+// that can't be written with Unicode superscripts (e.g. `m^(1/2)`) as they are
+fn units(s: &str) -> String {
+    let mut words = vec![];
+    for word in s.split(' ') {
+        let word = match word.find('^') {
+            Some(idx) => match word[idx + 1..]
+                .chars()
+                .map(superscript)
+                .collect::<Option<String>>()
+            {
+                Some(exponent) if idx + 1 < word.len() => format!("{}{}", &word[..idx], exponent),
+                _ => word.to_string(),
+            },
+            None => word.to_string(),
+        };
+        words.push(escape(&word));
+    }
+    words.join(" ")
+}
+
+/// Converts the spans of a result to Markdown, with numbers in bold and
+/// unit exponents written as Unicode superscripts, e.g. `**3** m²`
+pub(crate) fn spans_to_markdown(spans: &[Span]) -> String {
+    let mut result = String::new();
+    for span in spans {
+        match span.kind {
+            SpanKind::Number => {
+                result.push_str("**");
+                result.push_str(&escape(&span.string));
+                result.push_str("**");
+            }
+            SpanKind::Ident if span.string == "approx. " => result.push_str("\u{2248} "),
+            SpanKind::Ident => result.push_str(&units(&span.string)),
+            SpanKind::String
+            | SpanKind::Date
+            | SpanKind::Whitespace
+            | SpanKind::BuiltInFunction
+            | SpanKind::Keyword
+            | SpanKind::Operator
+            | SpanKind::Error
+            | SpanKind::Other => result.push_str(&escape(&span.string)),
+        }
+    }
+    result
+}
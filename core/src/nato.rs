@@ -0,0 +1,71 @@
+const LETTERS: [&str; 26] = [
+    "Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliett",
+    "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo", "Sierra", "Tango",
+    "Uniform", "Victor", "Whiskey", "X-ray", "Yankee", "Zulu",
+];
+
+const DIGITS: [&str; 10] = [
+    "Zero", "One", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine",
+];
+
+const DECIMAL: &str = "Decimal";
+
+/// Spells out a string using the NATO phonetic alphabet, e.g. `fend`
+/// becomes `Foxtrot Echo November Delta`. Digits and decimal points
+/// are spelled out too, other characters are kept as they are and
+/// whitespace is skipped.
+pub(crate) fn to_nato(s: &str) -> String {
+    let mut words = vec![];
+    for ch in s.chars() {
+        if ch.is_ascii_alphabetic() {
+            words.push(LETTERS[usize::from(ch.to_ascii_uppercase() as u8 - b'A')].to_string());
+        } else if ch.is_ascii_digit() {
+            words.push(DIGITS[usize::from(ch as u8 - b'0')].to_string());
+        } else if ch == '.' {
+            words.push(DECIMAL.to_string());
+        } else if !ch.is_whitespace() {
+            words.push(ch.to_string());
+        }
+    }
+    words.join(" ")
+}
+
+fn from_word(word: &str) -> Option<char> {
+    let word = word.to_ascii_lowercase();
+    // common alternative spellings
+    match word.as_str() {
+        "alpha" => return Some('A'),
+        "juliet" => return Some('J'),
+        "xray" => return Some('X'),
+        "niner" => return Some('9'),
+        _ => (),
+    }
+    if word == DECIMAL.to_ascii_lowercase() {
+        return Some('.');
+    }
+    for (letter, code_word) in (b'A'..=b'Z').zip(&LETTERS) {
+        if code_word.to_ascii_lowercase() == word {
+            return Some(char::from(letter));
+        }
+    }
+    for (digit, code_word) in (b'0'..=b'9').zip(&DIGITS) {
+        if code_word.to_ascii_lowercase() == word {
+            return Some(char::from(digit));
+        }
+    }
+    None
+}
+
+/// Converts words of the NATO phonetic alphabet back to the characters
+/// they represent, e.g. `Foxtrot Echo November Delta` becomes `FEND`.
+/// Unknown words are kept as they are.
+pub(crate) fn from_nato(s: &str) -> String {
+    let mut result = String::new();
+    for word in s.split_whitespace() {
+        match from_word(word) {
+            Some(ch) => result.push(ch),
+            None => result.push_str(word),
+        }
+    }
+    result
+}
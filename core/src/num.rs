@@ -6,12 +6,17 @@ mod biguint;
 mod complex;
 mod exact;
 mod formatting_style;
+mod measurement;
 mod real;
 mod unit;
+mod words;
 
 pub(crate) use formatting_style::FormattingStyle;
+pub(crate) use words::{fraction_to_words, to_ordinal_words};
 
 pub(crate) type Number<'a> = unit::Value<'a>;
+pub(crate) type Complex = complex::Complex;
+pub(crate) type Measurement<'a> = measurement::Measurement<'a>;
 pub(crate) type Base = base::Base;
 pub(crate) type Exact<T> = exact::Exact<T>;
 pub(crate) type BaseOutOfRangeError = base::OutOfRangeError;
@@ -105,6 +105,113 @@ impl BigRat {
         }
     }
 
+    pub(crate) fn try_as_biguint<I: Interrupt>(
+        mut self,
+        int: &I,
+    ) -> Result<BigUint, IntErr<ConvertToUsizeError, I>> {
+        if self.sign == Sign::Negative && self.num != 0.into() {
+            return Err(ConvertToUsizeError::NegativeNumber.into());
+        }
+        self = self.simplify(int)?;
+        if self.den != 1.into() {
+            return Err(ConvertToUsizeError::Fraction.into());
+        }
+        Ok(self.num)
+    }
+
+    /// Returns the integer part of the absolute value of this number,
+    /// e.g. `7/2` and `-7/2` both return `3`.
+    pub(crate) fn integer_part<I: Interrupt>(&self, int: &I) -> Result<BigUint, IntErr<Never, I>> {
+        Ok(self.num.divmod(&self.den, int).map_err(IntErr::unwrap)?.0)
+    }
+
+    /// Finds a fraction with a much smaller denominator that is within
+    /// rounding distance of this number, e.g. `2333/1000` becomes `7/3`.
+    /// The new denominator `q` must satisfy `q^2 <= den`, otherwise
+    /// the number is returned unchanged.
+    pub(crate) fn simplest_fraction<I: Interrupt>(self, int: &I) -> Result<Self, IntErr<Never, I>> {
+        use std::convert::TryFrom;
+        let simplified = self.simplify(int)?;
+        let (num, den) = match (
+            simplified.num.try_as_usize(int),
+            simplified.den.try_as_usize(int),
+        ) {
+            (Ok(num), Ok(den)) => (num as u128, den as u128),
+            _ => return Ok(simplified),
+        };
+        // continued fraction convergents p/q of num/den
+        let (mut p_prev, mut q_prev, mut p, mut q) = (0_u128, 1_u128, 1_u128, 0_u128);
+        let (mut a, mut b) = (num, den);
+        while b != 0 {
+            test_int(int)?;
+            let term = a / b;
+            let (p_next, q_next) = (term * p + p_prev, term * q + q_prev);
+            if q_next * q_next > den {
+                break;
+            }
+            // accept p/q if |num/den - p/q| < 1/(2 den)
+            let error = (num * q_next).max(p_next * den) - (num * q_next).min(p_next * den);
+            if 2 * error < q_next {
+                if let (Ok(p), Ok(q)) = (u64::try_from(p_next), u64::try_from(q_next)) {
+                    return Ok(Self {
+                        sign: simplified.sign,
+                        num: BigUint::from(p),
+                        den: BigUint::from(q),
+                    });
+                }
+            }
+            p_prev = p;
+            q_prev = q;
+            p = p_next;
+            q = q_next;
+            let remainder = a % b;
+            a = b;
+            b = remainder;
+        }
+        Ok(simplified)
+    }
+
+    /// Returns up to `count` terms of the continued fraction of the absolute
+    /// value of this number, e.g. `[3, 7, 15, 1]` for `355/113`
+    pub(crate) fn continued_fraction<I: Interrupt>(
+        self,
+        count: u64,
+        int: &I,
+    ) -> Result<Vec<BigUint>, IntErr<Never, I>> {
+        let simplified = self.simplify(int)?;
+        let (mut a, mut b) = (simplified.num, simplified.den);
+        let mut terms = vec![];
+        for _ in 0..count {
+            test_int(int)?;
+            if b == 0.into() {
+                break;
+            }
+            let (term, remainder) = a.divmod(&b, int).map_err(IntErr::unwrap)?;
+            terms.push(term);
+            a = b;
+            b = remainder;
+        }
+        Ok(terms)
+    }
+
+    /// Returns the numerator and denominator of this fraction in lowest
+    /// terms. The sign is included in the numerator.
+    pub(crate) fn numerator_and_denominator<I: Interrupt>(
+        self,
+        int: &I,
+    ) -> Result<(String, String), IntErr<Never, I>> {
+        let simplified = self.simplify(int)?;
+        let sign = if simplified.sign == Sign::Negative && simplified.num != 0.into() {
+            "-"
+        } else {
+            ""
+        };
+        Ok((
+            format!("{}{}", sign, simplified.num.fm(int)?),
+            simplified.den.fm(int)?.to_string(),
+        ))
+    }
+
     #[allow(clippy::float_arithmetic)]
     pub(crate) fn into_f64<I: Interrupt>(mut self, int: &I) -> Result<f64, IntErr<Never, I>> {
         self = self.simplify(int)?;
@@ -1092,7 +1199,7 @@ impl Format for BigRat {
         x.sign = Sign::Positive;
 
         // try as integer if possible
-        if x.den == 1.into() {
+        if x.den == 1.into() && style != FormattingStyle::AlwaysRatio {
             let sf_limit = if let FormattingStyle::SignificantFigures(sf) = style {
                 Some(sf)
             } else {
@@ -1119,6 +1226,7 @@ impl Format for BigRat {
             Some(t) => Ok(t),
         };
         let fraction = style == FormattingStyle::ImproperFraction
+            || style == FormattingStyle::AlwaysRatio
             || style == FormattingStyle::MixedFraction
             || (style == FormattingStyle::Exact && !terminating()?);
         if fraction {
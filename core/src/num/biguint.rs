@@ -21,6 +21,23 @@ const fn truncate(n: u128) -> u64 {
     n as u64
 }
 
+fn is_prime(n: u64) -> bool {
+    if n < 4 {
+        return n >= 2;
+    }
+    if n.is_multiple_of(2) {
+        return false;
+    }
+    let mut i = 3;
+    while i <= n / i {
+        if n.is_multiple_of(i) {
+            return false;
+        }
+        i += 2;
+    }
+    true
+}
+
 impl BigUint {
     fn is_zero(&self) -> bool {
         match self {
@@ -331,6 +348,28 @@ impl BigUint {
         Ok((q, r))
     }
 
+    /// Divides by a number that fits into a single word, which is much
+    /// faster than the binary long division in `divmod`
+    fn divmod_small(&self, divisor: u64) -> (Self, u64) {
+        let divisor = u128::from(divisor);
+        let mut quotient = vec![0; self.value_len()];
+        let mut remainder = 0;
+        for i in (0..self.value_len()).rev() {
+            let current = remainder << 64 | u128::from(self.get(i));
+            quotient[i] = truncate(current / divisor);
+            remainder = current % divisor;
+        }
+        while quotient.len() > 1 && quotient.last() == Some(&0) {
+            quotient.pop();
+        }
+        let quotient = if quotient.len() == 1 {
+            Small(quotient[0])
+        } else {
+            Large(quotient)
+        };
+        (quotient, truncate(remainder))
+    }
+
     /// computes self *= other
     fn mul_internal<I: Interrupt>(
         &mut self,
@@ -383,6 +422,53 @@ impl BigUint {
         Ok(res)
     }
 
+    /// Computes the least common multiple of all integers from 1 to `self`
+    pub(crate) fn lcm_range<I: Interrupt>(&self, int: &I) -> Result<Self, IntErr<Never, I>> {
+        let mut res = Self::from(1);
+        let mut i = Self::from(2);
+        while i <= *self {
+            test_int(int)?;
+            let gcd = Self::gcd(res.clone(), i.clone(), int)?;
+            res = res
+                .mul(&i, int)?
+                .div(&gcd, int)
+                .map_err(|e| e.expect("unexpected division by zero"))?;
+            i = i.add(&1.into());
+        }
+        Ok(res)
+    }
+
+    /// Computes the product of all primes less than or equal to `n`
+    pub(crate) fn primorial<I: Interrupt>(n: u64, int: &I) -> Result<Self, IntErr<Never, I>> {
+        let mut res = Self::from(1);
+        for i in 2..=n {
+            test_int(int)?;
+            if is_prime(i) {
+                res = res.mul(&i.into(), int)?;
+            }
+        }
+        Ok(res)
+    }
+
+    /// Counts the steps of the Collatz sequence starting at `self` (which
+    /// must not be zero) until it reaches 1
+    pub(crate) fn collatz_steps<I: Interrupt>(mut self, int: &I) -> Result<u64, IntErr<Never, I>> {
+        let mut steps = 0;
+        while self > 1.into() {
+            test_int(int)?;
+            let (half, rem) = self
+                .divmod(&2.into(), int)
+                .map_err(|e| e.expect("unexpected division by zero"))?;
+            self = if rem.is_zero() {
+                half
+            } else {
+                self.mul(&3.into(), int)?.add(&1.into())
+            };
+            steps += 1;
+        }
+        Ok(steps)
+    }
+
     pub(crate) fn mul<I: Interrupt>(
         mut self,
         other: &Self,
@@ -413,6 +499,112 @@ impl BigUint {
         Ok(self.divmod(other, int)?.0)
     }
 
+    /// Returns the number of digits needed to write this number in the
+    /// given base. Zero is written with a single digit.
+    pub(crate) fn digit_count<I: Interrupt>(
+        &self,
+        base: &Self,
+        int: &I,
+    ) -> Result<u64, IntErr<DivideByZero, I>> {
+        let mut count = 1;
+        let mut n = self.clone().div(base, int)?;
+        while !n.is_zero() {
+            test_int(int)?;
+            count += 1;
+            n = n.div(base, int)?;
+        }
+        Ok(count)
+    }
+
+    /// Reverses the order of the digits of this number in the given base.
+    /// Leading zeroes in the result are dropped.
+    pub(crate) fn reverse_digits<I: Interrupt>(
+        &self,
+        base: &Self,
+        int: &I,
+    ) -> Result<Self, IntErr<DivideByZero, I>> {
+        let mut result = Self::from(0);
+        let mut n = self.clone();
+        while !n.is_zero() {
+            test_int(int)?;
+            let (quotient, digit) = n.divmod(base, int)?;
+            result = result.mul(base, int)?.add(&digit);
+            n = quotient;
+        }
+        Ok(result)
+    }
+
+    /// Interleaves the bits of two numbers, with the bits of `self` placed at
+    /// even positions and the bits of `other` placed at odd positions.
+    pub(crate) fn interleave_bits<I: Interrupt>(
+        &self,
+        other: &Self,
+        int: &I,
+    ) -> Result<Self, IntErr<Never, I>> {
+        let two = Self::from(2);
+        let mut result = Self::from(0);
+        let mut place = Self::from(1);
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !a.is_zero() || !b.is_zero() {
+            test_int(int)?;
+            let (a_quotient, a_bit) = a.divmod(&two, int).map_err(IntErr::unwrap)?;
+            let (b_quotient, b_bit) = b.divmod(&two, int).map_err(IntErr::unwrap)?;
+            result = result.add(&a_bit.mul(&place, int)?);
+            place = place.mul(&two, int)?;
+            result = result.add(&b_bit.mul(&place, int)?);
+            place = place.mul(&two, int)?;
+            a = a_quotient;
+            b = b_quotient;
+        }
+        Ok(result)
+    }
+
+    /// Returns the number of one bits in this number.
+    pub(crate) fn count_ones(&self) -> u64 {
+        (0..self.value_len())
+            .map(|idx| u64::from(self.get(idx).count_ones()))
+            .sum()
+    }
+
+    /// Encodes this number as binary-coded decimal, where each group of
+    /// four bits holds a single decimal digit.
+    pub(crate) fn bcd_encode<I: Interrupt>(&self, int: &I) -> Result<Self, IntErr<Never, I>> {
+        let mut result = Self::from(0);
+        let mut place = Self::from(1);
+        let mut n = self.clone();
+        while !n.is_zero() {
+            test_int(int)?;
+            let (quotient, digit) = n.divmod(&10.into(), int).map_err(IntErr::unwrap)?;
+            result = result.add(&digit.mul(&place, int)?);
+            place = place.mul(&16.into(), int)?;
+            n = quotient;
+        }
+        Ok(result)
+    }
+
+    /// Decodes a binary-coded decimal number. Returns `None` if any group of
+    /// four bits holds a value that is not a decimal digit.
+    pub(crate) fn bcd_decode<I: Interrupt>(
+        &self,
+        int: &I,
+    ) -> Result<Option<Self>, IntErr<Never, I>> {
+        let mut result = Self::from(0);
+        let mut place = Self::from(1);
+        let mut n = self.clone();
+        while !n.is_zero() {
+            test_int(int)?;
+            let (quotient, digit) = n.divmod(&16.into(), int).map_err(IntErr::unwrap)?;
+            if digit >= 10.into() {
+                return Ok(None);
+            }
+            result = result.add(&digit.mul(&place, int)?);
+            place = place.mul(&10.into(), int)?;
+            n = quotient;
+        }
+        Ok(Some(result))
+    }
+
     pub(crate) fn add(mut self, other: &Self) -> Self {
         self.add_assign_internal(other, 1, 0);
         self
@@ -584,35 +776,26 @@ impl Format for BigUint {
                     true,
                 )
             } else {
-                let base_as_u128: u128 = params.base.base_as_u8().into();
-                let mut divisor = base_as_u128;
+                let base_as_u64: u64 = params.base.base_as_u8().into();
+                let mut divisor = base_as_u64;
                 let mut rounds = 1;
                 // note that the string is reversed: this is the number of trailing zeroes while
                 // printing, but actually the number of leading zeroes in the final number
                 let mut num_trailing_zeroes = 0;
                 let mut num_leading_zeroes = 0;
                 let mut finished_counting_leading_zeroes = false;
-                while divisor
-                    < u128::MAX
-                        .checked_div(base_as_u128)
-                        .expect("base appears to be 0")
-                {
-                    divisor *= base_as_u128;
+                while divisor <= u64::MAX / base_as_u64 {
+                    divisor *= base_as_u64;
                     rounds += 1;
                 }
-                let divisor = Self::Large(vec![truncate(divisor), truncate(divisor >> 64)]);
                 let mut output = String::with_capacity(rounds);
                 while !num.is_zero() {
                     test_int(int)?;
-                    let divmod_res = num
-                        .divmod(&divisor, int)
-                        .map_err(|e| e.expect("division by zero is not allowed"))?;
-                    let mut digit_group_value =
-                        u128::from(divmod_res.1.get(1)) << 64 | u128::from(divmod_res.1.get(0));
+                    let (quotient, mut digit_group_value) = num.divmod_small(divisor);
                     for _ in 0..rounds {
-                        let digit_value = digit_group_value % base_as_u128;
-                        digit_group_value /= base_as_u128;
-                        let ch = Base::digit_as_char(truncate(digit_value)).unwrap();
+                        let digit_value = digit_group_value % base_as_u64;
+                        digit_group_value /= base_as_u64;
+                        let ch = Base::digit_as_char(digit_value).unwrap();
                         if ch == '0' {
                             num_trailing_zeroes += 1;
                         } else {
@@ -627,7 +810,7 @@ impl Format for BigUint {
                             output.push(ch);
                         }
                     }
-                    num = divmod_res.0;
+                    num = quotient;
                 }
                 let exact = params
                     .sf_limit
@@ -1,4 +1,5 @@
 use crate::error::{IntErr, Interrupt, Never};
+use crate::num::biguint::BigUint;
 use crate::num::real::{self, Real};
 use crate::num::Exact;
 use crate::num::{Base, ConvertToUsizeError, DivideByZero, FormattingStyle};
@@ -40,6 +41,52 @@ impl Complex {
         self.real.try_as_usize(int)
     }
 
+    pub(crate) fn try_as_biguint<I: Interrupt>(
+        self,
+        int: &I,
+    ) -> Result<BigUint, IntErr<ConvertToUsizeError, I>> {
+        if self.imag != 0.into() {
+            return Err(ConvertToUsizeError::ComplexNumber.into());
+        }
+        self.real.try_as_biguint(int)
+    }
+
+    pub(crate) fn simplest_fraction<I: Interrupt>(self, int: &I) -> Result<Self, IntErr<Never, I>> {
+        Ok(Self {
+            real: self.real.simplest_fraction(int)?,
+            imag: self.imag.simplest_fraction(int)?,
+        })
+    }
+
+    pub(crate) fn numerator_and_denominator<I: Interrupt>(
+        self,
+        int: &I,
+    ) -> Result<Option<(String, String)>, IntErr<Never, I>> {
+        if self.imag != 0.into() {
+            return Ok(None);
+        }
+        self.real.numerator_and_denominator(int)
+    }
+
+    /// Returns the integer part of the absolute value of the real component
+    pub(crate) fn integer_part<I: Interrupt>(self, int: &I) -> Result<BigUint, IntErr<Never, I>> {
+        self.real.integer_part(int)
+    }
+
+    /// Returns the continued fraction terms of the absolute value of the
+    /// real component
+    pub(crate) fn continued_fraction<I: Interrupt>(
+        self,
+        count: u64,
+        int: &I,
+    ) -> Result<Vec<BigUint>, IntErr<Never, I>> {
+        self.real.continued_fraction(count, int)
+    }
+
+    pub(crate) fn is_real(&self) -> bool {
+        self.imag == 0.into()
+    }
+
     pub(crate) fn conjugate(self) -> Self {
         Self {
             real: self.real,
@@ -458,6 +505,15 @@ impl From<u64> for Complex {
     }
 }
 
+impl From<BigUint> for Complex {
+    fn from(n: BigUint) -> Self {
+        Self {
+            real: n.into(),
+            imag: 0.into(),
+        }
+    }
+}
+
 impl From<Real> for Complex {
     fn from(i: Real) -> Self {
         Self {
@@ -5,6 +5,8 @@ use std::fmt;
 pub(crate) enum FormattingStyle {
     /// Print value as an improper fraction
     ImproperFraction,
+    /// Print as an improper fraction, including integers, e.g. 2/1
+    AlwaysRatio,
     /// Print as a mixed fraction, e.g. 1 1/2
     MixedFraction,
     /// Print as a float, possibly indicating recurring digits
@@ -20,6 +22,19 @@ pub(crate) enum FormattingStyle {
     /// If not exact: DecimalPlaces(10). If no recurring digits: ExactFloat.
     /// Other numbers: MixedFraction, albeit possibly including fractions of pi
     Exact,
+    /// Print as an amount of money with two decimal places and grouped
+    /// thousands, e.g. $1,234.50
+    Currency,
+    /// Print an angle in degrees, minutes and seconds, e.g. 1°30′0″
+    DegreesMinutesSeconds,
+    /// Print a duration in hours, minutes and seconds, e.g. 1h 1m 1.5s
+    HoursMinutesSeconds,
+    /// Print a duration as a time on a 24-hour clock, e.g. 13:01:01
+    Clock24,
+    /// Print a duration as a time on a 12-hour clock, e.g. 01:01 PM
+    Clock12,
+    /// Print in engineering notation using SI prefix symbols, e.g. 1.234 km
+    EngineeringPrefixed,
 }
 
 impl Default for FormattingStyle {
@@ -32,12 +47,19 @@ impl fmt::Display for FormattingStyle {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         match self {
             Self::ImproperFraction => write!(f, "fraction"),
+            Self::AlwaysRatio => write!(f, "ratio"),
             Self::MixedFraction => write!(f, "mixed_fraction"),
             Self::ExactFloat => write!(f, "float"),
             Self::Exact => write!(f, "exact"),
             Self::DecimalPlaces(d) => write!(f, "{} dp", d),
             Self::SignificantFigures(s) => write!(f, "{} sf", s),
             Self::Auto => write!(f, "auto"),
+            Self::Currency => write!(f, "currency"),
+            Self::DegreesMinutesSeconds => write!(f, "dms"),
+            Self::HoursMinutesSeconds => write!(f, "hms"),
+            Self::Clock24 => write!(f, "clock"),
+            Self::Clock12 => write!(f, "clock_12h"),
+            Self::EngineeringPrefixed => write!(f, "engineering_prefixed"),
         }
     }
 }
@@ -46,12 +68,19 @@ impl fmt::Debug for FormattingStyle {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         match self {
             Self::ImproperFraction => write!(f, "improper fraction"),
+            Self::AlwaysRatio => write!(f, "ratio"),
             Self::MixedFraction => write!(f, "mixed fraction"),
             Self::ExactFloat => write!(f, "exact float"),
             Self::Exact => write!(f, "exact"),
             Self::DecimalPlaces(d) => write!(f, "{} dp", d),
             Self::SignificantFigures(s) => write!(f, "{} sf", s),
             Self::Auto => write!(f, "auto"),
+            Self::Currency => write!(f, "currency"),
+            Self::DegreesMinutesSeconds => write!(f, "dms"),
+            Self::HoursMinutesSeconds => write!(f, "hms"),
+            Self::Clock24 => write!(f, "24-hour clock"),
+            Self::Clock12 => write!(f, "12-hour clock"),
+            Self::EngineeringPrefixed => write!(f, "engineering notation with SI prefixes"),
         }
     }
 }
@@ -0,0 +1,156 @@
+use crate::error::{IntErr, Interrupt};
+use crate::num::{Base, FormattingStyle, Number};
+use crate::{Span, SpanKind};
+use std::ops::Neg;
+
+/// A number with an absolute uncertainty, e.g. `9.8 ± 0.1`. The
+/// uncertainty is always non-negative and in the same unit as the value.
+///
+/// Uncertainties are propagated linearly (i.e. as a worst case), so
+/// absolute uncertainties are added for sums and differences, and
+/// relative uncertainties are added for products and quotients.
+#[derive(Clone, Debug)]
+pub(crate) struct Measurement<'a> {
+    value: Number<'a>,
+    uncertainty: Number<'a>,
+}
+
+impl<'a> Measurement<'a> {
+    pub(crate) fn new<I: Interrupt>(
+        value: Number<'a>,
+        uncertainty: Number<'a>,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let uncertainty = uncertainty.convert_to_unit_of(&value, int)?;
+        if uncertainty.is_negative() {
+            return Err("uncertainty cannot be negative".to_string().into());
+        }
+        Ok(Self { value, uncertainty })
+    }
+
+    /// Treats an ordinary number as a measurement without any uncertainty.
+    pub(crate) fn exact<I: Interrupt>(
+        value: Number<'a>,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        // multiplying by zero keeps the unit of the value
+        let uncertainty = value.clone().mul(Number::from(0), int)?.abs(int)?;
+        Ok(Self { value, uncertainty })
+    }
+
+    pub(crate) fn add<I: Interrupt>(self, rhs: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        Self::new(
+            self.value.add(rhs.value, int)?,
+            self.uncertainty.add(rhs.uncertainty, int)?,
+            int,
+        )
+    }
+
+    pub(crate) fn sub<I: Interrupt>(self, rhs: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        Self::new(
+            self.value.sub(rhs.value, int)?,
+            self.uncertainty.add(rhs.uncertainty, int)?,
+            int,
+        )
+    }
+
+    pub(crate) fn mul<I: Interrupt>(self, rhs: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let relative_uncertainty = self
+            .relative_uncertainty(int)?
+            .add(rhs.relative_uncertainty(int)?, int)?;
+        let value = self.value.mul(rhs.value, int)?;
+        let uncertainty = value.clone().abs(int)?.mul(relative_uncertainty, int)?;
+        Self::new(value, uncertainty, int)
+    }
+
+    pub(crate) fn div<I: Interrupt>(self, rhs: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let relative_uncertainty = self
+            .relative_uncertainty(int)?
+            .add(rhs.relative_uncertainty(int)?, int)?;
+        let value = self.value.div(rhs.value, int)?;
+        let uncertainty = value.clone().abs(int)?.mul(relative_uncertainty, int)?;
+        Self::new(value, uncertainty, int)
+    }
+
+    /// Raises this measurement to the power of an exact number, which
+    /// multiplies the relative uncertainty by the absolute value of the
+    /// exponent
+    pub(crate) fn pow<I: Interrupt>(
+        self,
+        exponent: Number<'a>,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        if self.value.is_zero() {
+            return Err("cannot raise a measurement of zero to a power"
+                .to_string()
+                .into());
+        }
+        let relative_uncertainty = self
+            .relative_uncertainty(int)?
+            .mul(exponent.clone().abs(int)?, int)?;
+        let value = self.value.pow(exponent, int)?;
+        let uncertainty = value.clone().abs(int)?.mul(relative_uncertainty, int)?;
+        Self::new(value, uncertainty, int)
+    }
+
+    pub(crate) fn convert_to<I: Interrupt>(
+        self,
+        unit: Number<'a>,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let value = self.value.convert_to(unit, int)?;
+        let uncertainty = self.uncertainty.convert_to_unit_of(&value, int)?;
+        Ok(Self { value, uncertainty })
+    }
+
+    pub(crate) fn try_with_format(self, format: FormattingStyle) -> Result<Self, String> {
+        Ok(Self {
+            value: self.value.try_with_format(format)?,
+            uncertainty: self.uncertainty.try_with_format(format)?,
+        })
+    }
+
+    pub(crate) fn with_base(self, base: Base) -> Self {
+        Self {
+            value: self.value.with_base(base),
+            uncertainty: self.uncertainty.with_base(base),
+        }
+    }
+
+    fn relative_uncertainty<I: Interrupt>(&self, int: &I) -> Result<Number<'a>, IntErr<String, I>> {
+        if self.uncertainty.is_zero() {
+            return Ok(Number::from(0));
+        }
+        self.uncertainty
+            .clone()
+            .div(self.value.clone().abs(int)?, int)
+    }
+
+    pub(crate) fn spans<I: Interrupt>(
+        &self,
+        spans: &mut Vec<Span>,
+        int: &I,
+    ) -> Result<(), IntErr<String, I>> {
+        self.value.clone().simplify(int)?.format(int)?.spans(spans);
+        spans.push(Span {
+            string: " \u{b1} ".to_string(),
+            kind: SpanKind::Operator,
+        });
+        self.uncertainty
+            .clone()
+            .simplify(int)?
+            .format(int)?
+            .spans(spans);
+        Ok(())
+    }
+}
+
+impl Neg for Measurement<'_> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            value: -self.value,
+            uncertainty: self.uncertainty,
+        }
+    }
+}
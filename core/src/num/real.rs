@@ -1,6 +1,7 @@
 use crate::error::{IntErr, Interrupt, Never};
 use crate::format::Format;
 use crate::num::bigrat::{BigRat, FormattedBigRat};
+use crate::num::biguint::BigUint;
 use crate::num::Exact;
 use crate::num::{Base, ConvertToUsizeError, DivideByZero, FormattingStyle, ValueOutOfRange};
 use std::cmp::Ordering;
@@ -94,6 +95,52 @@ impl Real {
         }
     }
 
+    pub(crate) fn try_as_biguint<I: Interrupt>(
+        self,
+        int: &I,
+    ) -> Result<BigUint, IntErr<ConvertToUsizeError, I>> {
+        match self.pattern {
+            Pattern::Simple(s) => s.try_as_biguint(int),
+            Pattern::Pi(n) => {
+                if n == 0.into() {
+                    Ok(0.into())
+                } else {
+                    Err(ConvertToUsizeError::InvalidRealNumber.into())
+                }
+            }
+        }
+    }
+
+    pub(crate) fn integer_part<I: Interrupt>(self, int: &I) -> Result<BigUint, IntErr<Never, I>> {
+        self.approximate(int)?.integer_part(int)
+    }
+
+    pub(crate) fn continued_fraction<I: Interrupt>(
+        self,
+        count: u64,
+        int: &I,
+    ) -> Result<Vec<BigUint>, IntErr<Never, I>> {
+        self.approximate(int)?.continued_fraction(count, int)
+    }
+
+    pub(crate) fn simplest_fraction<I: Interrupt>(self, int: &I) -> Result<Self, IntErr<Never, I>> {
+        Ok(match self.pattern {
+            Pattern::Simple(s) => Self::from(s.simplest_fraction(int)?),
+            Pattern::Pi(_) => self,
+        })
+    }
+
+    /// Returns `None` for multiples of pi, which are irrational
+    pub(crate) fn numerator_and_denominator<I: Interrupt>(
+        self,
+        int: &I,
+    ) -> Result<Option<(String, String)>, IntErr<Never, I>> {
+        Ok(match self.pattern {
+            Pattern::Simple(s) => Some(s.numerator_and_denominator(int)?),
+            Pattern::Pi(_) => None,
+        })
+    }
+
     // sin works for all real numbers
     pub(crate) fn sin<I: Interrupt>(self, int: &I) -> Result<Exact<Self>, IntErr<Never, I>> {
         Ok(match self.pattern {
@@ -465,6 +512,14 @@ impl From<u64> for Real {
     }
 }
 
+impl From<BigUint> for Real {
+    fn from(n: BigUint) -> Self {
+        Self {
+            pattern: Pattern::Simple(n.into()),
+        }
+    }
+}
+
 impl From<BigRat> for Real {
     fn from(n: BigRat) -> Self {
         Self {
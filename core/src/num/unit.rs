@@ -1,17 +1,55 @@
 use crate::error::{IntErr, Interrupt, Never};
 use crate::interrupt::test_int;
+use crate::num::biguint::BigUint;
 use crate::num::complex::{self, Complex, UseParentheses};
 use crate::num::{Base, ConvertToUsizeError, FormattingStyle};
 use crate::scope::Scope;
 use crate::{ast, ident::Ident};
 use crate::{Span, SpanKind};
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt;
 use std::ops::Neg;
 use std::sync::Arc;
 
 use super::Exact;
 
+/// SI prefixes with their symbol, name and power of ten
+const SI_PREFIXES: &[(&str, &str, i32)] = &[
+    ("y", "yocto", -24),
+    ("z", "zepto", -21),
+    ("a", "atto", -18),
+    ("f", "femto", -15),
+    ("p", "pico", -12),
+    ("n", "nano", -9),
+    ("\u{b5}", "micro", -6),
+    ("m", "milli", -3),
+    ("c", "centi", -2),
+    ("d", "deci", -1),
+    ("", "", 0),
+    ("da", "deka", 1),
+    ("h", "hecto", 2),
+    ("k", "kilo", 3),
+    ("M", "mega", 6),
+    ("G", "giga", 9),
+    ("T", "tera", 12),
+    ("P", "peta", 15),
+    ("E", "exa", 18),
+    ("Z", "zetta", 21),
+    ("Y", "yotta", 24),
+];
+
+fn si_prefix_power(prefix: &str) -> Option<i32> {
+    if prefix == "u" || prefix == "\u{3bc}" {
+        return Some(-6);
+    }
+    SI_PREFIXES
+        .iter()
+        .find(|(symbol, name, _)| prefix == *symbol || prefix == *name)
+        .map(|(_, _, power)| *power)
+}
+
 #[derive(Clone)]
 pub(crate) struct Value<'a> {
     value: Complex,
@@ -36,6 +74,186 @@ impl<'a> Value<'a> {
         self.value.try_as_usize(int)
     }
 
+    pub(crate) fn try_as_u64<I: Interrupt>(self, int: &I) -> Result<u64, IntErr<String, I>> {
+        let n = self.try_as_usize(int).map_err(IntErr::into_string)?;
+        u64::try_from(n).map_err(|_| "number is out of range".to_string().into())
+    }
+
+    pub(crate) fn try_as_u32<I: Interrupt>(self, int: &I) -> Result<u32, IntErr<String, I>> {
+        let n = self.try_as_usize(int).map_err(IntErr::into_string)?;
+        u32::try_from(n).map_err(|_| "number is out of range".to_string().into())
+    }
+
+    /// Applies a function to this number as a 64-bit integer. The result
+    /// keeps the base used for display.
+    pub(crate) fn map_u64<I: Interrupt>(
+        self,
+        f: impl FnOnce(u64) -> Result<u64, String>,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let base = self.base;
+        let n = self.try_as_u64(int)?;
+        Ok(Self::from(f(n)?).with_base(base))
+    }
+
+    pub(crate) fn try_as_i64<I: Interrupt>(self, int: &I) -> Result<i64, IntErr<String, I>> {
+        let negative = self.value < 0.into();
+        let magnitude = if negative { -self } else { self }
+            .try_as_usize(int)
+            .map_err(IntErr::into_string)?;
+        let magnitude =
+            i64::try_from(magnitude).map_err(|_| "number is out of range".to_string())?;
+        Ok(if negative { -magnitude } else { magnitude })
+    }
+
+    pub(crate) fn try_as_biguint<I: Interrupt>(
+        self,
+        int: &I,
+    ) -> Result<BigUint, IntErr<ConvertToUsizeError, I>> {
+        if !self.is_unitless() {
+            return Err(ConvertToUsizeError::NumberWithUnit.into());
+        }
+        if !self.exact {
+            return Err(ConvertToUsizeError::InexactNumber.into());
+        }
+        self.value.try_as_biguint(int)
+    }
+
+    /// Returns the numerator and denominator of the (possibly approximate)
+    /// value of this number, or `None` if it is complex or a multiple of pi.
+    pub(crate) fn numerator_and_denominator<I: Interrupt>(
+        self,
+        int: &I,
+    ) -> Result<Option<(String, String)>, IntErr<String, I>> {
+        Ok(self.simplify(int)?.value.numerator_and_denominator(int)?)
+    }
+
+    /// Returns the value of this number without its unit, e.g. `5` for `5 km`
+    pub(crate) fn into_complex(self) -> Complex {
+        self.value
+    }
+
+    /// Spells out this number in English words, e.g. `3/4` becomes
+    /// `three quarters`
+    pub(crate) fn spell_out<I: Interrupt>(self, int: &I) -> Result<String, IntErr<String, I>> {
+        if !self.is_unitless() {
+            return Err("only unitless numbers can be written as words"
+                .to_string()
+                .into());
+        }
+        let is_negative = self.is_negative();
+        let exact = self.exact;
+        let (numerator, denominator) = self
+            .abs(int)?
+            .numerator_and_denominator(int)?
+            .ok_or_else(|| "unable to write this number as words".to_string())?;
+        let parse = |s: &str| {
+            s.parse::<u64>()
+                .map_err(|_| "unable to write this number as words".to_string())
+        };
+        let (numerator, denominator) = (parse(&numerator)?, parse(&denominator)?);
+        if !exact && denominator != 1 {
+            return Err("unable to write this number as words".to_string().into());
+        }
+        let words = crate::num::fraction_to_words(numerator, denominator);
+        Ok(if is_negative {
+            format!("minus {}", words)
+        } else {
+            words
+        })
+    }
+
+    // converts a number to a base for use in digit-based functions
+    fn try_as_digit_base<I: Interrupt>(self, int: &I) -> Result<BigUint, IntErr<String, I>> {
+        let base = self.try_as_biguint(int).map_err(IntErr::into_string)?;
+        if base < 2.into() {
+            return Err("base must be at least 2".to_string().into());
+        }
+        Ok(base)
+    }
+
+    /// Returns the number of digits of this integer when written in the
+    /// given base, e.g. 255 has 2 digits in base 16.
+    pub(crate) fn digit_count<I: Interrupt>(
+        self,
+        base: Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let base = base.try_as_digit_base(int)?;
+        let n = self.try_as_biguint(int).map_err(IntErr::into_string)?;
+        let count = n.digit_count(&base, int).map_err(IntErr::into_string)?;
+        Ok(Self::from(count))
+    }
+
+    /// Reverses the digits of this integer in the given base, e.g. 1234
+    /// becomes 4321 in base 10. The result keeps the base used for display.
+    pub(crate) fn reverse_digits<I: Interrupt>(
+        self,
+        base: Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let digit_base = base.try_as_digit_base(int)?;
+        let display_base = self.base;
+        let n = self.try_as_biguint(int).map_err(IntErr::into_string)?;
+        let reversed = n
+            .reverse_digits(&digit_base, int)
+            .map_err(IntErr::into_string)?;
+        Ok(Self::from(reversed).with_base(display_base))
+    }
+
+    /// Computes the Morton code (Z-order curve index) of two integers by
+    /// interleaving their bits. The result is displayed in hexadecimal.
+    pub(crate) fn interleave_bits<I: Interrupt>(
+        self,
+        other: Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let a = self.try_as_biguint(int).map_err(IntErr::into_string)?;
+        let b = other.try_as_biguint(int).map_err(IntErr::into_string)?;
+        Ok(Self::from(a.interleave_bits(&b, int)?).with_base(Base::HEX))
+    }
+
+    pub(crate) fn count_ones<I: Interrupt>(self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let n = self.try_as_biguint(int).map_err(IntErr::into_string)?;
+        Ok(Self::from(n.count_ones()))
+    }
+
+    pub(crate) fn lcm_range<I: Interrupt>(self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let n = self.try_as_biguint(int).map_err(IntErr::into_string)?;
+        Ok(Self::from(n.lcm_range(int)?))
+    }
+
+    pub(crate) fn primorial<I: Interrupt>(self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let n = self.try_as_u64(int)?;
+        Ok(Self::from(BigUint::primorial(n, int)?))
+    }
+
+    pub(crate) fn collatz<I: Interrupt>(self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let n = self.try_as_biguint(int).map_err(IntErr::into_string)?;
+        if n == BigUint::from(0) {
+            return Err("collatz requires a positive integer".to_string().into());
+        }
+        Ok(Self::from(n.collatz_steps(int)?))
+    }
+
+    /// Converts a decimal integer to binary-coded decimal, e.g. 123
+    /// becomes 0x123. The result is displayed in hexadecimal.
+    pub(crate) fn bcd_encode<I: Interrupt>(self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let n = self.try_as_biguint(int).map_err(IntErr::into_string)?;
+        Ok(Self::from(n.bcd_encode(int)?).with_base(Base::HEX))
+    }
+
+    /// Converts a binary-coded decimal number back to a decimal integer.
+    pub(crate) fn bcd_decode<I: Interrupt>(self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let n = self.try_as_biguint(int).map_err(IntErr::into_string)?;
+        match n.bcd_decode(int)? {
+            Some(decoded) => Ok(Self::from(decoded)),
+            None => Err("number is not valid binary-coded decimal"
+                .to_string()
+                .into()),
+        }
+    }
+
     pub(crate) fn create_unit_value_from_value<I: Interrupt>(
         value: &Self,
         prefix: &'a str,
@@ -60,6 +278,18 @@ impl<'a> Value<'a> {
         Self::new(1, vec![UnitExponent::new(unit, 1)])
     }
 
+    /// Returns a string that identifies this number, e.g. for caching
+    /// results. Numbers that are equal and would be displayed the same
+    /// way produce the same key, regardless of their internal
+    /// representation.
+    pub(crate) fn cache_key<I: Interrupt>(&self, int: &I) -> Result<String, IntErr<Never, I>> {
+        let exact = self
+            .clone()
+            .with_format(FormattingStyle::Exact)
+            .format(int)?;
+        Ok(format!("{} ({:?}, {:?})", exact, self.base, self.format))
+    }
+
     pub(crate) fn with_format(self, format: FormattingStyle) -> Self {
         Self {
             value: self.value,
@@ -71,6 +301,121 @@ impl<'a> Value<'a> {
         }
     }
 
+    /// Like `with_format`, but checks that the number can be displayed
+    /// in the given format. Only amounts of money (or dimensionless
+    /// numbers) can be formatted as currency.
+    pub(crate) fn try_with_format(self, format: FormattingStyle) -> Result<Self, String> {
+        if format == FormattingStyle::Currency {
+            if !self.value.is_real() {
+                return Err("cannot format a complex number as currency".to_string());
+            }
+            if !self.unit.components.is_empty() && !self.is_amount_of_money() {
+                return Err("only amounts of money can be formatted as currency".to_string());
+            }
+        }
+        if format == FormattingStyle::EngineeringPrefixed {
+            if !self.value.is_real() {
+                return Err("cannot use SI prefixes for a complex number".to_string());
+            }
+            let supported = match self.unit.components.as_slice() {
+                [] => true,
+                [unit_exponent] => {
+                    unit_exponent.exponent == 1.into()
+                        && si_prefix_power(unit_exponent.unit.prefix).is_some()
+                }
+                _ => false,
+            };
+            if !supported {
+                return Err(
+                    "SI prefixes can only be used with a single unit, e.g. `m` or `g`".to_string(),
+                );
+            }
+        }
+        Ok(self.with_format(format))
+    }
+
+    // e.g. `100 USD`, but not `100 USD/kg`
+    pub(crate) fn is_amount_of_money(&self) -> bool {
+        match self.unit.components.as_slice() {
+            [unit_exponent] => {
+                unit_exponent.exponent == 1.into() && unit_exponent.unit.is_currency()
+            }
+            _ => false,
+        }
+    }
+
+    /// Converts an amount of money using the given exchange rate instead of
+    /// the built-in rates, e.g. `100 USD` to `EUR` at a rate of 0.92 is `92 EUR`
+    pub(crate) fn convert_currency<I: Interrupt>(
+        self,
+        to: Self,
+        rate: Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        if !self.is_amount_of_money() || !to.is_amount_of_money() {
+            return Err("expected an amount of money and a currency"
+                .to_string()
+                .into());
+        }
+        if !rate.is_unitless() {
+            return Err("exchange rate must not have a unit".to_string().into());
+        }
+        let amount = Self {
+            value: self.value,
+            unit: Unit::unitless(),
+            exact: self.exact,
+            base: self.base,
+            format: self.format,
+            simplifiable: self.simplifiable,
+        };
+        amount.mul(rate, int)?.mul(to, int)
+    }
+
+    /// Converts an angle to degrees or a duration to hours, to be
+    /// displayed in sexagesimal form (e.g. `1°30′0″` or `1h 1m 1.5s`)
+    pub(crate) fn convert_to_sexagesimal<I: Interrupt>(
+        self,
+        unit: Self,
+        format: FormattingStyle,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let (quantity, description) = if format == FormattingStyle::HoursMinutesSeconds {
+            ("durations", "hours, minutes and seconds")
+        } else {
+            ("angles", "degrees, minutes and seconds")
+        };
+        if !self.value.is_real() {
+            return Err(format!("cannot format a complex number in {}", description).into());
+        }
+        Ok(self
+            .convert_to(unit, int)
+            .map_err(|e| {
+                e.map(|_| format!("only {} can be formatted in {}", quantity, description))
+            })?
+            .with_format(format))
+    }
+
+    /// Converts a duration to seconds, to be displayed as a time on a
+    /// 24-hour or 12-hour clock
+    pub(crate) fn convert_to_clock<I: Interrupt>(
+        self,
+        second: Self,
+        format: FormattingStyle,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        if !self.value.is_real() || self.is_negative() {
+            return Err(
+                "only non-negative durations can be formatted as a clock time"
+                    .to_string()
+                    .into(),
+            );
+        }
+        Ok(self
+            .convert_to(second, int)
+            .map_err(|e| e.map(|_| "only durations can be formatted as a clock time".to_string()))?
+            .with_format(format))
+    }
+
     pub(crate) fn with_base(self, base: Base) -> Self {
         Self {
             value: self.value,
@@ -98,6 +443,194 @@ impl<'a> Value<'a> {
         })
     }
 
+    /// Returns `1/n!`, i.e. the `n`th term of the series for e.
+    pub(crate) fn reciprocal_factorial<I: Interrupt>(
+        self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        Self::from(1).div(self.factorial(int)?, int)
+    }
+
+    /// Computes the partial sum `1/0! + 1/1! + ... + 1/(n-1)!` of the series
+    /// for e, where `n` (the number of terms) is given by `self`.
+    pub(crate) fn series_e<I: Interrupt>(self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let terms = self.try_as_usize(int).map_err(IntErr::into_string)?;
+        let mut sum = Self::from(0);
+        let mut k = Self::from(0);
+        for _ in 0..terms {
+            test_int(int)?;
+            sum = sum.add(k.clone().reciprocal_factorial(int)?, int)?;
+            k = k.add(Self::from(1), int)?;
+        }
+        Ok(sum)
+    }
+
+    /// Formats the number with exactly the given number of decimal places,
+    /// rounding half away from zero, like JavaScript's `toFixed`.
+    pub(crate) fn format_fixed<I: Interrupt>(
+        self,
+        decimal_places: usize,
+        int: &I,
+    ) -> Result<String, IntErr<String, I>> {
+        if !self.value.is_real() {
+            return Err(
+                "only real numbers can be formatted with fixed decimal places"
+                    .to_string()
+                    .into(),
+            );
+        }
+        let value = self
+            .simplify(int)?
+            .with_format(FormattingStyle::DecimalPlaces(decimal_places));
+        let mut formatted = value.format(int)?;
+        if !formatted.exact {
+            formatted = value.round_half_up(0, decimal_places, int)?.format(int)?;
+        }
+        let mut number = formatted.number;
+        if decimal_places > 0 {
+            let existing_places = if let Some(idx) = number.find('.') {
+                number.len() - idx - 1
+            } else {
+                number.push('.');
+                0
+            };
+            for _ in existing_places..decimal_places {
+                number.push('0');
+            }
+        }
+        Ok(format!("{}{}", number, formatted.unit_str))
+    }
+
+    /// Converts the number into the unit of `other`, e.g. `2 cm` into
+    /// `0.02 m` if `other` is in metres.
+    pub(crate) fn convert_to_unit_of<I: Interrupt>(
+        self,
+        other: &Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let zero = Self {
+            value: Complex::from(0),
+            exact: true,
+            ..other.clone()
+        };
+        zero.add(self, int)
+    }
+
+    /// Formats a measurement like `9.8 ± 0.1`, rounding both numbers to the
+    /// decimal place of the first significant digit of the uncertainty.
+    pub(crate) fn format_with_uncertainty<I: Interrupt>(
+        self,
+        uncertainty: Self,
+        int: &I,
+    ) -> Result<String, IntErr<String, I>> {
+        let uncertainty = uncertainty.convert_to_unit_of(&self, int)?;
+        if !uncertainty.value.is_real() || uncertainty.value <= 0.into() {
+            return Err("uncertainty must be positive".to_string().into());
+        }
+        let mut decimal_places = 0;
+        let ten = Exact::new(Complex::from(10), true);
+        let mut scaled = Exact::new(uncertainty.value.clone(), true);
+        while scaled.value < 1.into() {
+            test_int(int)?;
+            scaled = scaled.mul(&ten, int)?;
+            decimal_places += 1;
+        }
+        Ok(format!(
+            "{} ± {}",
+            self.format_fixed(decimal_places, int)?,
+            uncertainty.format_fixed(decimal_places, int)?
+        ))
+    }
+
+    /// Formats the number with exactly the given number of significant
+    /// figures, rounding half away from zero, like JavaScript's `toPrecision`.
+    pub(crate) fn format_precision<I: Interrupt>(
+        self,
+        significant_figures: usize,
+        int: &I,
+    ) -> Result<String, IntErr<String, I>> {
+        if !self.value.is_real() {
+            return Err(
+                "only real numbers can be formatted with a fixed number of significant figures"
+                    .to_string()
+                    .into(),
+            );
+        }
+        let value = self
+            .simplify(int)?
+            .with_format(FormattingStyle::SignificantFigures(significant_figures));
+        let mut formatted = value.format(int)?;
+        if !formatted.exact {
+            let number = formatted.number.trim_start_matches('-');
+            let (integer_part, fractional_part) = match number.find('.') {
+                Some(idx) => (&number[..idx], &number[idx + 1..]),
+                None => (number, ""),
+            };
+            let integer_digits = integer_part.trim_start_matches('0').len();
+            // find the position of the last significant digit, relative to
+            // the decimal point
+            formatted = if integer_digits == 0 {
+                let leading_zeroes = fractional_part.chars().take_while(|&c| c == '0').count();
+                value.round_half_up(0, leading_zeroes + significant_figures, int)?
+            } else {
+                value.round_half_up(
+                    integer_digits.saturating_sub(significant_figures),
+                    significant_figures.saturating_sub(integer_digits),
+                    int,
+                )?
+            }
+            .format(int)?;
+        }
+        let mut number = formatted.number;
+        let digits = number
+            .chars()
+            .skip_while(|&c| c == '-' || c == '0' || c == '.')
+            .filter(char::is_ascii_alphanumeric)
+            .count();
+        if digits < significant_figures {
+            if !number.contains('.') {
+                number.push('.');
+            }
+            for _ in digits..significant_figures {
+                number.push('0');
+            }
+        }
+        Ok(format!("{}{}", number, formatted.unit_str))
+    }
+
+    /// Adds (or for negative numbers subtracts) half of `10^exponent`,
+    /// where the exponent is given as `positive_exponent - negative_exponent`.
+    /// Truncating the result to that digit then rounds to the nearest value.
+    fn round_half_up<I: Interrupt>(
+        self,
+        positive_exponent: usize,
+        negative_exponent: usize,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let mut offset = Self::from(1).div(Self::from(2), int)?;
+        for _ in 0..positive_exponent {
+            test_int(int)?;
+            offset = offset.mul(Self::from(10), int)?;
+        }
+        for _ in 0..negative_exponent {
+            test_int(int)?;
+            offset = offset.div(Self::from(10), int)?;
+        }
+        let offset = Self {
+            value: offset.value,
+            unit: self.unit.clone(),
+            exact: true,
+            base: self.base,
+            format: self.format,
+            simplifiable: self.simplifiable,
+        };
+        if self.value < 0.into() {
+            self.sub(offset, int)
+        } else {
+            self.add(offset, int)
+        }
+    }
+
     fn new(value: impl Into<Complex>, unit_components: Vec<UnitExponent<'a>>) -> Self {
         Self {
             value: value.into(),
@@ -171,6 +704,21 @@ impl<'a> Value<'a> {
         })
     }
 
+    pub(crate) fn compare<I: Interrupt>(
+        self,
+        rhs: Self,
+        int: &I,
+    ) -> Result<Ordering, IntErr<String, I>> {
+        let difference = self.sub(rhs, int)?;
+        if !difference.value.is_real() {
+            return Err("cannot compare complex numbers".to_string().into());
+        }
+        Ok(difference
+            .value
+            .partial_cmp(&0.into())
+            .unwrap_or(Ordering::Equal))
+    }
+
     pub(crate) fn div<I: Interrupt>(self, rhs: Self, int: &I) -> Result<Self, IntErr<String, I>> {
         let mut components = self.unit.components.clone();
         for rhs_component in rhs.unit.components {
@@ -192,7 +740,7 @@ impl<'a> Value<'a> {
         })
     }
 
-    fn is_unitless(&self) -> bool {
+    pub(crate) fn is_unitless(&self) -> bool {
         // todo this is broken for unitless components
         self.unit.components.is_empty()
     }
@@ -266,6 +814,148 @@ impl<'a> Value<'a> {
         })
     }
 
+    /// Rounds towards zero while keeping the unit, e.g. `-3.7 kg` becomes `-3 kg`
+    pub(crate) fn integer_part<I: Interrupt>(self, int: &I) -> Result<Self, IntErr<String, I>> {
+        if !self.value.is_real() {
+            return Err(
+                "only real numbers can be split into integer and fractional parts"
+                    .to_string()
+                    .into(),
+            );
+        }
+        let mut value = Complex::from(self.value.clone().integer_part(int)?);
+        if self.is_negative() {
+            value = -value;
+        }
+        Ok(Self {
+            value,
+            unit: self.unit,
+            exact: self.exact,
+            base: self.base,
+            format: self.format,
+            simplifiable: self.simplifiable,
+        })
+    }
+
+    /// The remainder after rounding towards zero, e.g. `-3.7 kg` becomes `-0.7 kg`
+    pub(crate) fn fractional_part<I: Interrupt>(self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let integer_part = self.clone().integer_part(int)?;
+        self.sub(integer_part, int)
+    }
+
+    /// Splits this number into the exponent and mantissa of its scientific
+    /// notation in the given base, e.g. `1234` is `1.234 * 10^3` in base 10
+    pub(crate) fn exponent_and_mantissa<I: Interrupt>(
+        self,
+        base: &Self,
+        int: &I,
+    ) -> Result<(Self, Self), IntErr<String, I>> {
+        if !self.is_unitless() || !base.is_unitless() {
+            return Err(
+                "exponent and mantissa are only supported for unitless numbers"
+                    .to_string()
+                    .into(),
+            );
+        }
+        if !self.value.is_real() || !base.value.is_real() {
+            return Err("exponent and mantissa are only supported for real numbers"
+                .to_string()
+                .into());
+        }
+        if self.is_zero() {
+            return Err("zero has no exponent or mantissa".to_string().into());
+        }
+        if base.clone().compare(Self::from(1), int)? != Ordering::Greater {
+            return Err("base must be greater than 1".to_string().into());
+        }
+        let negative = self.is_negative();
+        let mut mantissa = self.abs(int)?;
+        let mut exponent: i64 = 0;
+        while mantissa.clone().compare(base.clone(), int)? != Ordering::Less {
+            test_int(int)?;
+            mantissa = mantissa.div(base.clone(), int)?;
+            exponent += 1;
+        }
+        while mantissa.clone().compare(Self::from(1), int)? == Ordering::Less {
+            test_int(int)?;
+            mantissa = mantissa.mul(base.clone(), int)?;
+            exponent -= 1;
+        }
+        let magnitude = Self::from(exponent.unsigned_abs());
+        Ok((
+            if exponent < 0 { -magnitude } else { magnitude },
+            if negative { -mantissa } else { mantissa },
+        ))
+    }
+
+    /// Returns the first `count` convergents of the continued fraction of
+    /// this number, e.g. `3/1`, `22/7`, `333/106` and `355/113` for pi.
+    /// Fewer convergents are returned if the continued fraction terminates.
+    pub(crate) fn continued_fraction_convergents<I: Interrupt>(
+        self,
+        count: u64,
+        int: &I,
+    ) -> Result<Vec<Self>, IntErr<String, I>> {
+        if !self.is_unitless() || !self.value.is_real() {
+            return Err("convergents are only supported for real unitless numbers"
+                .to_string()
+                .into());
+        }
+        let negative = self.is_negative();
+        let (mut p_prev, mut q_prev) = (Self::from(0), Self::from(1));
+        let (mut p, mut q) = (Self::from(1), Self::from(0));
+        let mut convergents = vec![];
+        for term in self.value.continued_fraction(count, int)? {
+            let term = Self::from(term);
+            let p_next = term.clone().mul(p.clone(), int)?.add(p_prev, int)?;
+            let q_next = term.mul(q.clone(), int)?.add(q_prev, int)?;
+            p_prev = p;
+            q_prev = q;
+            p = p_next;
+            q = q_next;
+            let convergent = p.clone().div(q.clone(), int)?;
+            convergents.push(
+                if negative { -convergent } else { convergent }
+                    .with_format(FormattingStyle::AlwaysRatio),
+            );
+        }
+        Ok(convergents)
+    }
+
+    /// Evaluates the Bernstein basis polynomial `C(n, k) * t^k * (1 - t)^(n - k)`,
+    /// e.g. `0.5` for `n = 2`, `k = 1` and `t = 0.5`
+    pub(crate) fn bernstein_polynomial<I: Interrupt>(
+        n: u64,
+        k: u64,
+        t: &Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        if k > n {
+            return Err("k must not be greater than n".to_string().into());
+        }
+        if !t.is_unitless()
+            || !t.value.is_real()
+            || t.is_negative()
+            || t.clone().compare(Self::from(1), int)? == Ordering::Greater
+        {
+            return Err("t must be between 0 and 1".to_string().into());
+        }
+        let one_minus_t = Self::from(1).sub(t.clone(), int)?;
+        let mut result = Self::from(1);
+        for i in 1..=k {
+            test_int(int)?;
+            result = result
+                .mul(Self::from(n - k + i), int)?
+                .div(Self::from(i), int)?
+                .mul(t.clone(), int)?;
+        }
+        for _ in k..n {
+            test_int(int)?;
+            result = result.mul(one_minus_t.clone(), int)?;
+        }
+        Ok(result)
+    }
+
     pub(crate) fn make_approximate(self) -> Self {
         Self {
             value: self.value,
@@ -292,6 +982,10 @@ impl<'a> Value<'a> {
         self.value == 0.into()
     }
 
+    pub(crate) fn is_negative(&self) -> bool {
+        self.value < 0.into()
+    }
+
     fn apply_fn_exact<I: Interrupt>(
         self,
         f: impl FnOnce(Complex, &I) -> Result<Exact<Complex>, IntErr<String, I>>,
@@ -414,6 +1108,32 @@ impl<'a> Value<'a> {
         self.apply_fn(Complex::atan, false, int)
     }
 
+    /// Computes the angle (in radians) of the point `(x, self)`, between
+    /// -pi and pi
+    pub(crate) fn atan2<I: Interrupt>(self, x: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let x = x.convert_to_unit_of(&self, int)?;
+        if x.is_zero() {
+            let half_pi = Self::pi().div(Self::from(2), int)?;
+            return Ok(if self.is_negative() {
+                -half_pi
+            } else if self.is_zero() {
+                Self::from(0)
+            } else {
+                half_pi
+            });
+        }
+        let y_is_negative = self.is_negative();
+        let x_is_negative = x.is_negative();
+        let angle = self.div(x, int)?.atan(int)?;
+        if !x_is_negative {
+            Ok(angle)
+        } else if y_is_negative {
+            angle.sub(Self::pi(), int)
+        } else {
+            angle.add(Self::pi(), int)
+        }
+    }
+
     pub(crate) fn sinh<I: Interrupt>(self, int: &I) -> Result<Self, IntErr<String, I>> {
         self.apply_fn(Complex::sinh, false, int)
     }
@@ -450,15 +1170,260 @@ impl<'a> Value<'a> {
         self.apply_fn(Complex::log10, true, int)
     }
 
+    // formats e.g. `-1234.567 USD` as `-$1,234.57`
+    fn format_currency<I: Interrupt>(&self, int: &I) -> Result<FormattedValue, IntErr<Never, I>> {
+        // round half away from zero by adding or subtracting 0.005
+        let mut offset = Exact::new(Complex::from(1), true)
+            .div(Exact::new(Complex::from(200), true), int)
+            .map_err(IntErr::unwrap)?
+            .value;
+        let negative = self.value < 0.into();
+        if negative {
+            offset = -offset;
+        }
+        let rounded = Exact::new(self.value.clone(), true)
+            .add(Exact::new(offset, true), int)?
+            .value;
+        let formatted = rounded.format(
+            true,
+            FormattingStyle::DecimalPlaces(2),
+            Base::default(),
+            UseParentheses::No,
+            int,
+        )?;
+        let number = formatted.value.to_string();
+        let number = number.trim_start_matches('-');
+        let (integer_part, fractional_part) = match number.find('.') {
+            Some(idx) => (&number[..idx], &number[idx + 1..]),
+            None => (number, ""),
+        };
+        let mut result = String::new();
+        let is_zero = integer_part.trim_start_matches('0').is_empty()
+            && fractional_part.trim_start_matches('0').is_empty();
+        if negative && !is_zero {
+            result.push('-');
+        }
+        let symbol = match self.unit.components.first() {
+            Some(unit_exponent) => unit_exponent.unit.currency_symbol(),
+            None => String::new(),
+        };
+        result.push_str(&symbol);
+        for (i, ch) in integer_part.chars().enumerate() {
+            if i != 0 && (integer_part.len() - i) % 3 == 0 {
+                result.push(',');
+            }
+            result.push(ch);
+        }
+        result.push('.');
+        result.push_str(fractional_part);
+        for _ in fractional_part.len()..2 {
+            result.push('0');
+        }
+        Ok(FormattedValue {
+            number: result,
+            exact: true,
+            unit_str: String::new(),
+        })
+    }
+
+    // formats an angle in degrees as e.g. `1°30′0″`, or a duration in
+    // seconds as e.g. `1h 1m 1.5s`, where the seconds may have a fractional part
+    fn format_sexagesimal<I: Interrupt>(
+        &self,
+        symbols: [&str; 3],
+        separator: &str,
+        int: &I,
+    ) -> Result<FormattedValue, IntErr<Never, I>> {
+        let negative = self.value < 0.into();
+        let value = if negative {
+            -self.value.clone()
+        } else {
+            self.value.clone()
+        };
+        let sixty = Exact::new(Complex::from(60), true);
+        let whole = value.clone().integer_part(int)?;
+        let minutes = Exact::new(value, self.exact)
+            .add(Exact::new(-Complex::from(whole.clone()), true), int)?
+            .mul(&sixty, int)?;
+        let whole_minutes = minutes.value.clone().integer_part(int)?;
+        let seconds = minutes
+            .add(Exact::new(-Complex::from(whole_minutes.clone()), true), int)?
+            .mul(&sixty, int)?;
+        let mut result = String::new();
+        if negative {
+            result.push('-');
+        }
+        for (part, symbol) in [whole, whole_minutes].iter().zip(&symbols) {
+            let formatted = Complex::from(part.clone()).format(
+                true,
+                FormattingStyle::Auto,
+                Base::default(),
+                UseParentheses::No,
+                int,
+            )?;
+            result.push_str(&formatted.value.to_string());
+            result.push_str(symbol);
+            result.push_str(separator);
+        }
+        let formatted_seconds = seconds.value.format(
+            seconds.exact,
+            FormattingStyle::Auto,
+            Base::default(),
+            UseParentheses::No,
+            int,
+        )?;
+        result.push_str(&formatted_seconds.value.to_string());
+        result.push_str(symbols[2]);
+        Ok(FormattedValue {
+            number: result,
+            exact: formatted_seconds.exact,
+            unit_str: String::new(),
+        })
+    }
+
+    // formats a number of seconds as the time of day it represents, e.g.
+    // `13:01:01` or `01:01 PM`, dropping any full days and fractional seconds
+    fn format_clock<I: Interrupt>(&self, int: &I) -> Result<FormattedValue, IntErr<Never, I>> {
+        let seconds = self
+            .value
+            .clone()
+            .integer_part(int)?
+            .divmod(&86400.into(), int)
+            .map_err(|e| e.expect("unexpected division by zero"))?
+            .1
+            .try_as_usize(int)
+            .map_err(|e| e.expect("seconds in a day should fit in a usize"))?;
+        let (hours, minutes) = (seconds / 3600, seconds / 60 % 60);
+        let number = if self.format == FormattingStyle::Clock12 {
+            let suffix = if hours < 12 { "AM" } else { "PM" };
+            let hours = if hours % 12 == 0 { 12 } else { hours % 12 };
+            format!("{:02}:{:02} {}", hours, minutes, suffix)
+        } else {
+            format!("{}:{:02}:{:02}", hours, minutes, seconds % 60)
+        };
+        Ok(FormattedValue {
+            number,
+            exact: true,
+            unit_str: String::new(),
+        })
+    }
+
+    // formats a number with the SI prefix that brings it into the range
+    // 1 to 1000, e.g. `1.234 km` for 1234 metres or `1.234 k` for 1234
+    fn format_engineering_prefixed<I: Interrupt>(
+        &self,
+        int: &I,
+    ) -> Result<FormattedValue, IntErr<Never, I>> {
+        let unit = self
+            .unit
+            .components
+            .first()
+            .map(|unit_exponent| &unit_exponent.unit);
+        let mut power = unit
+            .and_then(|unit| si_prefix_power(unit.prefix))
+            .unwrap_or(0);
+        let negative = self.value < 0.into();
+        let mut value = Exact::new(
+            if negative {
+                -self.value.clone()
+            } else {
+                self.value.clone()
+            },
+            self.exact,
+        );
+        let ten = Exact::new(Complex::from(10), true);
+        let thousand = Exact::new(Complex::from(1000), true);
+        while power % 3 != 0 {
+            value = value.mul(&ten, int)?;
+            power -= 1;
+        }
+        if value.value != 0.into() {
+            while value.value >= 1000.into() && power < 24 {
+                value = value.div(thousand.clone(), int).map_err(IntErr::unwrap)?;
+                power += 3;
+            }
+            while value.value < 1.into() && power > -24 {
+                value = value.mul(&thousand, int)?;
+                power -= 3;
+            }
+        }
+        if negative {
+            value = -value;
+        }
+        let formatted = value.value.format(
+            value.exact,
+            FormattingStyle::Auto,
+            self.base,
+            UseParentheses::No,
+            int,
+        )?;
+        let (symbol, name) = SI_PREFIXES
+            .iter()
+            .find(|(_, _, p)| *p == power)
+            .map_or(("", ""), |(symbol, name, _)| (*symbol, *name));
+        let unit_str = match unit {
+            Some(unit) => {
+                // units written out in full (which have a distinct plural
+                // form, like `metres`) also get the full prefix name
+                let prefix = if unit.prefix.chars().count() > 2
+                    || (unit.prefix.is_empty() && unit.singular_name != unit.plural_name)
+                {
+                    name
+                } else {
+                    symbol
+                };
+                let unit_name = if value.value == 1.into() {
+                    unit.singular_name
+                } else {
+                    unit.plural_name
+                };
+                let space = if unit.print_with_space() { " " } else { "" };
+                format!("{}{}{}", space, prefix, unit_name)
+            }
+            None if symbol.is_empty() => String::new(),
+            None => format!(" {}", symbol),
+        };
+        Ok(FormattedValue {
+            number: formatted.value.to_string(),
+            exact: formatted.exact,
+            unit_str,
+        })
+    }
+
     pub(crate) fn format<I: Interrupt>(&self, int: &I) -> Result<FormattedValue, IntErr<Never, I>> {
+        if self.format == FormattingStyle::Currency {
+            return self.format_currency(int);
+        }
+        if self.format == FormattingStyle::DegreesMinutesSeconds {
+            return self.format_sexagesimal(["\u{b0}", "\u{2032}", "\u{2033}"], "", int);
+        }
+        if self.format == FormattingStyle::HoursMinutesSeconds {
+            return self.format_sexagesimal(["h", "m", "s"], " ", int);
+        }
+        if let FormattingStyle::Clock24 | FormattingStyle::Clock12 = self.format {
+            return self.format_clock(int);
+        }
+        if self.format == FormattingStyle::EngineeringPrefixed {
+            return self.format_engineering_prefixed(int);
+        }
         let use_parentheses = if self.unit.components.is_empty() {
             UseParentheses::No
         } else {
             UseParentheses::IfComplex
         };
+        // approximate values like `approx. 2.333` are shown as nearby simple
+        // fractions (here `2 1/3`) rather than as `2 333/1000`
+        let value = if !self.exact
+            && (self.format == FormattingStyle::MixedFraction
+                || self.format == FormattingStyle::ImproperFraction
+                || self.format == FormattingStyle::AlwaysRatio)
+        {
+            self.value.clone().simplest_fraction(int)?
+        } else {
+            self.value.clone()
+        };
         let formatted_value =
-            self.value
-                .format(self.exact, self.format, self.base, use_parentheses, int)?;
+            value.format(self.exact, self.format, self.base, use_parentheses, int)?;
         let mut exact = formatted_value.exact;
         let mut unit_string = String::new();
         if !self.unit.components.is_empty() {
@@ -512,7 +1477,7 @@ impl<'a> Value<'a> {
             }
         }
         Ok(FormattedValue {
-            number: formatted_value.value,
+            number: formatted_value.value.to_string(),
             exact,
             unit_str: unit_string,
         })
@@ -637,6 +1602,19 @@ impl Neg for Value<'_> {
     }
 }
 
+impl From<BigUint> for Value<'_> {
+    fn from(n: BigUint) -> Self {
+        Self {
+            value: n.into(),
+            unit: Unit::unitless(),
+            exact: true,
+            base: Base::default(),
+            format: FormattingStyle::default(),
+            simplifiable: true,
+        }
+    }
+}
+
 impl From<u64> for Value<'_> {
     fn from(i: u64) -> Self {
         Self {
@@ -668,7 +1646,7 @@ impl<'a> fmt::Debug for Value<'a> {
 #[derive(Debug)]
 pub(crate) struct FormattedValue {
     exact: bool,
-    number: complex::Formatted,
+    number: String,
     unit_str: String,
 }
 
@@ -681,7 +1659,7 @@ impl FormattedValue {
             });
         }
         spans.push(Span {
-            string: self.number.to_string(),
+            string: self.number,
             kind: SpanKind::Number,
         });
         spans.push(Span {
@@ -959,6 +1937,28 @@ impl<'a> NamedUnit<'a> {
         }
     }
 
+    fn is_currency(&self) -> bool {
+        self.base_units.len() == 1
+            && self
+                .base_units
+                .iter()
+                .all(|(base_unit, exponent)| base_unit.name == "_EUR" && *exponent == 1.into())
+    }
+
+    // Used when formatting money, e.g. `$` for US dollars. Currencies without
+    // a well-known symbol are written using their name, e.g. `CHF 5.00`.
+    fn currency_symbol(&self) -> String {
+        match self.singular_name {
+            "USD" | "dollar" | "$" | "US$" => "$".to_string(),
+            "EUR" | "euro" | "\u{20ac}" => "\u{20ac}".to_string(),
+            "GBP" => "\u{a3}".to_string(),
+            "JPY" => "\u{a5}".to_string(),
+            "INR" => "\u{20b9}".to_string(),
+            "KRW" => "\u{20a9}".to_string(),
+            name => format!("{} ", name),
+        }
+    }
+
     /// Returns whether or not this unit should be printed with a
     /// space (between the number and the unit). This should be true for most
     /// units like kg or m, but not for % or °
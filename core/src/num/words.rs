@@ -0,0 +1,129 @@
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: [&str; 7] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+];
+
+// spells out a number between 1 and 999
+fn push_hundreds(words: &mut Vec<String>, n: usize) {
+    if n >= 100 {
+        words.push(ONES[n / 100].to_string());
+        words.push("hundred".to_string());
+    }
+    let n = n % 100;
+    if n == 0 {
+        return;
+    }
+    if n < 20 {
+        words.push(ONES[n].to_string());
+    } else if n.is_multiple_of(10) {
+        words.push(TENS[n / 10].to_string());
+    } else {
+        words.push(format!("{}-{}", TENS[n / 10], ONES[n % 10]));
+    }
+}
+
+/// Spells out a non-negative integer in English words, e.g. 123 becomes
+/// `one hundred twenty-three`
+pub(crate) fn to_words(n: u64) -> String {
+    if n == 0 {
+        return ONES[0].to_string();
+    }
+    let mut groups = vec![];
+    let mut remaining = n;
+    while remaining > 0 {
+        groups.push((remaining % 1000) as usize);
+        remaining /= 1000;
+    }
+    let mut words = vec![];
+    for (scale, &group) in groups.iter().enumerate().rev() {
+        if group == 0 {
+            continue;
+        }
+        push_hundreds(&mut words, group);
+        if scale > 0 {
+            words.push(SCALES[scale].to_string());
+        }
+    }
+    words.join(" ")
+}
+
+fn ordinal_word(word: &str) -> String {
+    match word {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        _ => match word.strip_suffix('y') {
+            Some(stem) => format!("{}ieth", stem),
+            None => format!("{}th", word),
+        },
+    }
+}
+
+/// Spells out a non-negative integer as an English ordinal, e.g. 21
+/// becomes `twenty-first`. Only the last word changes, so 1000000 becomes
+/// `one millionth`.
+pub(crate) fn to_ordinal_words(n: u64) -> String {
+    let cardinal = to_words(n);
+    let last_word_start = cardinal.rfind(&[' ', '-'][..]).map_or(0, |idx| idx + 1);
+    format!(
+        "{}{}",
+        &cardinal[..last_word_start],
+        ordinal_word(&cardinal[last_word_start..])
+    )
+}
+
+/// Spells out a fraction, e.g. 3/4 becomes `three quarters`
+pub(crate) fn fraction_to_words(numerator: u64, denominator: u64) -> String {
+    if denominator == 1 {
+        return to_words(numerator);
+    }
+    let denominator_word = match denominator {
+        2 => "half".to_string(),
+        4 => "quarter".to_string(),
+        _ => to_ordinal_words(denominator),
+    };
+    if numerator == 1 {
+        return format!("one {}", denominator_word);
+    }
+    let denominator_words = match denominator_word.strip_suffix("half") {
+        Some(stem) => format!("{}halves", stem),
+        None => format!("{}s", denominator_word),
+    };
+    format!("{} {}", to_words(numerator), denominator_words)
+}
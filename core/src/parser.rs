@@ -1,4 +1,4 @@
-use crate::ast::Expr;
+use crate::ast::{Comparison, Expr};
 use crate::lexer::{Symbol, Token};
 use std::fmt;
 
@@ -88,7 +88,9 @@ fn parse_ident<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
     match parse_token(input, true)? {
         (Token::Ident(ident), remaining) => {
             if let Ok(((), remaining2)) = parse_fixed_symbol(remaining, Symbol::Of) {
-                let (inner, remaining3) = parse_ident(remaining2)?;
+                // allow e.g. `day_of_year of ("2021-04-04" as date)`
+                let (inner, remaining3) =
+                    parse_ident(remaining2).or_else(|_| parse_parens(remaining2))?;
                 Ok((Expr::Of(ident, Box::new(inner)), remaining3))
             } else {
                 Ok((Expr::Ident(ident), remaining))
@@ -258,6 +260,12 @@ fn parse_division_cont<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
     Ok((b, input))
 }
 
+fn parse_composition_cont<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
+    let (_, input) = parse_fixed_symbol(input, Symbol::Compose)?;
+    let (b, input) = parse_power(input, true)?;
+    Ok((b, input))
+}
+
 fn parse_multiplicative<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
     let (mut res, mut input) = parse_power(input, true)?;
     loop {
@@ -267,6 +275,9 @@ fn parse_multiplicative<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
         } else if let Ok((term, remaining)) = parse_division_cont(input) {
             res = Expr::Div(Box::new(res.clone()), Box::new(term));
             input = remaining;
+        } else if let Ok((term, remaining)) = parse_composition_cont(input) {
+            res = Expr::Compose(Box::new(res.clone()), Box::new(term));
+            input = remaining;
         } else if let Ok((new_res, remaining)) = parse_mixed_fraction(input, &res) {
             res = new_res;
             input = remaining;
@@ -307,6 +318,12 @@ fn parse_subtraction_cont<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b>
     Ok((b, input))
 }
 
+fn parse_plus_minus_cont<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
+    let (_, input) = parse_fixed_symbol(input, Symbol::PlusMinus)?;
+    let (b, input) = parse_implicit_addition(input)?;
+    Ok((b, input))
+}
+
 fn parse_to_cont<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
     let (_, input) = parse_fixed_symbol(input, Symbol::ArrowConversion)?;
     let (b, input) = parse_implicit_addition(input)?;
@@ -322,6 +339,9 @@ fn parse_additive<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
         } else if let Ok((term, remaining)) = parse_subtraction_cont(input) {
             res = Expr::Sub(Box::new(res), Box::new(term));
             input = remaining;
+        } else if let Ok((term, remaining)) = parse_plus_minus_cont(input) {
+            res = Expr::PlusMinus(Box::new(res), Box::new(term));
+            input = remaining;
         } else if let Ok((term, remaining)) = parse_to_cont(input) {
             res = Expr::As(Box::new(res), Box::new(term));
             input = remaining;
@@ -332,8 +352,56 @@ fn parse_additive<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
     Ok((res, input))
 }
 
-fn parse_function<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
+fn parse_comparison_operator<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b, Comparison> {
+    let (token, remaining) = parse_token(input, true)?;
+    let comparison = match token {
+        Token::Symbol(Symbol::Less) => Comparison::Less,
+        Token::Symbol(Symbol::LessOrEqual) => Comparison::LessOrEqual,
+        Token::Symbol(Symbol::Greater) => Comparison::Greater,
+        Token::Symbol(Symbol::GreaterOrEqual) => Comparison::GreaterOrEqual,
+        Token::Symbol(Symbol::Equal) => Comparison::Equal,
+        Token::Symbol(Symbol::NotEqual) => Comparison::NotEqual,
+        _ => return Err(ParseError::UnexpectedInput),
+    };
+    Ok((comparison, remaining))
+}
+
+// comparisons can't be chained, so e.g. `1 < 2 < 3` is a parse error
+fn parse_comparison<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
     let (lhs, input) = parse_additive(input)?;
+    if let Ok((comparison, remaining)) = parse_comparison_operator(input) {
+        let (rhs, remaining) = parse_additive(remaining)?;
+        return Ok((
+            Expr::Compare(Box::new(lhs), comparison, Box::new(rhs)),
+            remaining,
+        ));
+    }
+    Ok((lhs, input))
+}
+
+fn parse_and<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
+    let (mut res, mut input) = parse_comparison(input)?;
+    while let Ok((_, remaining)) = parse_fixed_symbol(input, Symbol::And) {
+        let (rhs, remaining) = parse_comparison(remaining)?;
+        res = Expr::And(Box::new(res), Box::new(rhs));
+        input = remaining;
+    }
+    Ok((res, input))
+}
+
+// `and` binds more tightly than `or`, so `a or b and c` is `a or (b and c)`
+fn parse_or<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
+    let (mut res, mut input) = parse_and(input)?;
+    while let Ok((_, remaining)) = parse_fixed_symbol(input, Symbol::Or) {
+        let (rhs, remaining) = parse_and(remaining)?;
+        res = Expr::Or(Box::new(res), Box::new(rhs));
+        input = remaining;
+    }
+    Ok((res, input))
+}
+
+fn parse_function<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
+    let (lhs, input) = parse_or(input)?;
     if let Ok((_, remaining)) = parse_fixed_symbol(input, Symbol::Fn) {
         if let Expr::Ident(s) = lhs {
             let (rhs, remaining) = parse_function(remaining)?;
@@ -344,8 +412,22 @@ fn parse_function<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
     Ok((lhs, input))
 }
 
+fn parse_list<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
+    let (first, mut input) = parse_function(input)?;
+    if parse_fixed_symbol(input, Symbol::Comma).is_err() {
+        return Ok((first, input));
+    }
+    let mut items = vec![first];
+    while let Ok((_, remaining)) = parse_fixed_symbol(input, Symbol::Comma) {
+        let (item, remaining) = parse_function(remaining)?;
+        items.push(item);
+        input = remaining;
+    }
+    Ok((Expr::List(items), input))
+}
+
 pub(crate) fn parse_expression<'a, 'b>(input: &'b [Token<'a>]) -> ParseResult<'a, 'b> {
-    parse_function(input)
+    parse_list(input)
 }
 
 pub(crate) fn parse_tokens<'a, 'b>(input: &'b [Token<'a>]) -> Result<Expr<'a>, ParseError> {
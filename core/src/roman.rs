@@ -0,0 +1,135 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+const SYMBOLS: [(usize, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+const MAX_VALUE: usize = 3_999_999_999;
+
+#[derive(Debug)]
+pub(crate) enum RomanNumeralError {
+    Zero,
+    TooLarge,
+    Invalid,
+}
+
+impl fmt::Display for RomanNumeralError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Zero => write!(f, "zero cannot be represented as a roman numeral"),
+            Self::TooLarge => write!(
+                f,
+                "roman numerals are only supported for values up to {}",
+                MAX_VALUE
+            ),
+            Self::Invalid => write!(f, "invalid roman numeral"),
+        }
+    }
+}
+
+impl crate::error::Error for RomanNumeralError {}
+
+fn push_plain(out: &mut String, mut n: usize) {
+    for &(value, symbol) in &SYMBOLS {
+        while n >= value {
+            out.push_str(symbol);
+            n -= value;
+        }
+    }
+}
+
+// Each overline is written as a combining overline (U+0305) after every
+// numeral, i.e. `V\u{305}` is a V with a single overline (5000), and
+// `V\u{305}\u{305}` is a V with a double overline (5,000,000). Markdown-style
+// markers like `_V_` are not used because the markers of adjacent groups
+// would run together, e.g. `__MMM___CMXCIX_` for 3,999,999,000.
+fn push_overlined(out: &mut String, n: usize, overlines: usize) {
+    let mut plain = String::new();
+    push_plain(&mut plain, n);
+    for ch in plain.chars() {
+        out.push(ch);
+        for _ in 0..overlines {
+            out.push('\u{305}');
+        }
+    }
+}
+
+/// Converts a positive integer to roman numerals. Values of 4000 and
+/// above use vinculum notation, where each overline multiplies the
+/// overlined numerals by 1000.
+pub(crate) fn to_roman(n: usize) -> Result<String, RomanNumeralError> {
+    if n == 0 {
+        return Err(RomanNumeralError::Zero);
+    }
+    if n > MAX_VALUE {
+        return Err(RomanNumeralError::TooLarge);
+    }
+    let mut result = String::new();
+    if n < 4000 {
+        push_plain(&mut result, n);
+    } else if n < 4_000_000 {
+        push_overlined(&mut result, n / 1000, 1);
+        push_plain(&mut result, n % 1000);
+    } else {
+        push_overlined(&mut result, n / 1_000_000, 2);
+        push_overlined(&mut result, n / 1000 % 1000, 1);
+        push_plain(&mut result, n % 1000);
+    }
+    Ok(result)
+}
+
+fn symbol_value(ch: char) -> Option<u64> {
+    Some(match ch {
+        'I' => 1,
+        'V' => 5,
+        'X' => 10,
+        'L' => 50,
+        'C' => 100,
+        'D' => 500,
+        'M' => 1000,
+        _ => return None,
+    })
+}
+
+/// Parses roman numerals (without overlines), e.g. `XLII` is 42. Only
+/// numerals in standard form are accepted, so `IIII` is rejected.
+pub(crate) fn from_roman(s: &str) -> Result<u64, RomanNumeralError> {
+    let s = s.trim().to_uppercase();
+    let mut values = vec![];
+    for ch in s.chars() {
+        values.push(symbol_value(ch).ok_or(RomanNumeralError::Invalid)?);
+    }
+    let mut result = 0;
+    let mut values = values.into_iter().peekable();
+    while let Some(value) = values.next() {
+        match values.peek() {
+            Some(&next) if next > value => {
+                result += next - value;
+                values.next();
+            }
+            _ => result += value,
+        }
+    }
+    if result == 0 || result >= 4000 {
+        return Err(RomanNumeralError::Invalid);
+    }
+    // only accept the canonical representation of the parsed value
+    let canonical = to_roman(usize::try_from(result).map_err(|_| RomanNumeralError::Invalid)?)?;
+    if canonical != s {
+        return Err(RomanNumeralError::Invalid);
+    }
+    Ok(result)
+}
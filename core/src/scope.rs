@@ -4,14 +4,19 @@ use crate::{
     error::{IntErr, Interrupt},
 };
 use std::fmt;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Clone)]
 enum ScopeValue<'a> {
-    //Variable(Value),
-    LazyVariable(Expr<'a>, Option<Arc<Scope<'a>>>),
+    Variable(Value<'a>),
+    // evaluated the first time it is used, after which the result is
+    // shared by all uses (e.g. so that a function returned by `memo` keeps
+    // its cache)
+    LazyVariable(Expr<'a>, Option<Arc<Scope<'a>>>, LazyValue<'a>),
 }
 
+type LazyValue<'a> = Arc<Mutex<Option<Value<'a>>>>;
+
 #[derive(Debug)]
 pub(crate) enum GetIdentError<'a> {
     EvalError(String),
@@ -44,8 +49,13 @@ impl<'a> ScopeValue<'a> {
         int: &I,
     ) -> Result<Value<'a>, IntErr<String, I>> {
         match self {
-            Self::LazyVariable(expr, scope) => {
+            Self::Variable(value) => Ok(value.clone()),
+            Self::LazyVariable(expr, scope, cached) => {
+                if let Some(value) = &*cached.lock().expect("variable is poisoned") {
+                    return Ok(value.clone());
+                }
                 let value = crate::ast::evaluate(expr.clone(), scope.clone(), context, int)?;
+                *cached.lock().expect("variable is poisoned") = Some(value.clone());
                 Ok(value)
             }
         }
@@ -78,7 +88,53 @@ impl<'a> Scope<'a> {
         scope: Option<Arc<Self>>,
         inner: Option<Arc<Self>>,
     ) -> Self {
-        Self::with_scope_value(name, ScopeValue::LazyVariable(expr, scope), inner)
+        // if the expression is just another variable, refer to its value
+        // directly, so that recursive functions don't build up long chains
+        // of variables that each refer to the previous one
+        if let (Expr::Ident(ident), Some(scope)) = (&expr, &scope) {
+            if let Some(value) = scope.find(ident.as_str()) {
+                return Self::with_scope_value(name, value.clone(), inner);
+            }
+        }
+        Self::with_scope_value(
+            name,
+            ScopeValue::LazyVariable(expr, scope, LazyValue::default()),
+            inner,
+        )
+    }
+
+    pub(crate) fn with_value(name: &'a str, value: Value<'a>, inner: Option<Arc<Self>>) -> Self {
+        Self::with_scope_value(name, ScopeValue::Variable(value), inner)
+    }
+
+    fn find(&self, ident: &str) -> Option<&ScopeValue<'a>> {
+        if self.ident == ident {
+            Some(&self.value)
+        } else {
+            self.inner.as_ref().and_then(|inner| inner.find(ident))
+        }
+    }
+
+    /// Returns all variables that are in scope, innermost first, with
+    /// their values formatted as strings. Shadowed variables are skipped.
+    pub(crate) fn dump<I: Interrupt>(
+        &self,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Vec<(&'a str, String)>, IntErr<String, I>> {
+        let mut result: Vec<(&'a str, String)> = vec![];
+        let mut scope = Some(self);
+        while let Some(current) = scope {
+            if !result.iter().any(|(ident, _)| *ident == current.ident) {
+                let value = current
+                    .value
+                    .eval(context, int)?
+                    .format_to_plain_string(0, int)?;
+                result.push((current.ident, value));
+            }
+            scope = current.inner.as_deref();
+        }
+        Ok(result)
     }
 
     pub(crate) fn get<I: Interrupt>(
@@ -0,0 +1,61 @@
+// abbreviation, offset from UTC in minutes
+type TimeZone = (&'static str, i32);
+
+// Abbreviations that are ambiguous (e.g. CST or IST) use their most common meaning.
+const TIME_ZONES: &[TimeZone] = &[
+    ("UTC", 0),
+    ("GMT", 0),
+    ("WET", 0),
+    ("WEST", 60),
+    ("BST", 60),
+    ("CET", 60),
+    ("CEST", 120),
+    ("EET", 120),
+    ("EEST", 180),
+    ("MSK", 180),
+    ("GST", 240),
+    ("PKT", 300),
+    ("IST", 330),
+    ("NPT", 345),
+    ("ICT", 420),
+    ("WIB", 420),
+    ("HKT", 480),
+    ("SGT", 480),
+    ("AWST", 480),
+    ("JST", 540),
+    ("KST", 540),
+    ("ACST", 570),
+    ("ACDT", 630),
+    ("AEST", 600),
+    ("AEDT", 660),
+    ("NZST", 720),
+    ("NZDT", 780),
+    ("BRT", -180),
+    ("ART", -180),
+    ("NST", -210),
+    ("NDT", -150),
+    ("AST", -240),
+    ("ADT", -180),
+    ("EST", -300),
+    ("EDT", -240),
+    ("CST", -360),
+    ("CDT", -300),
+    ("MST", -420),
+    ("MDT", -360),
+    ("PST", -480),
+    ("PDT", -420),
+    ("AKST", -540),
+    ("AKDT", -480),
+    ("HST", -600),
+];
+
+/// Looks up the offset from UTC (in minutes) of a time zone abbreviation
+/// such as `EST` or `CET`. Daylight saving time is not taken into account.
+pub(crate) fn offset_in_minutes(name: &str) -> Result<i32, String> {
+    for (abbreviation, offset) in TIME_ZONES {
+        if abbreviation.eq_ignore_ascii_case(name.trim()) {
+            return Ok(*offset);
+        }
+    }
+    Err(format!("unknown time zone '{}'", name))
+}
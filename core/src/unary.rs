@@ -0,0 +1,33 @@
+const MAX_VALUE: usize = 10_000;
+
+/// Converts a non-negative integer to tally marks, written as groups of
+/// five `I` strokes separated by spaces, e.g. 7 becomes `IIIII II`
+pub(crate) fn to_unary(n: usize) -> Result<String, String> {
+    if n > MAX_VALUE {
+        return Err(format!(
+            "unary numbers are only supported for values up to {}",
+            MAX_VALUE
+        ));
+    }
+    let mut result = String::new();
+    for i in 0..n {
+        if i != 0 && i % 5 == 0 {
+            result.push(' ');
+        }
+        result.push('I');
+    }
+    Ok(result)
+}
+
+/// Counts the tally marks in a string like `IIII`, ignoring whitespace
+pub(crate) fn from_unary(s: &str) -> Result<u64, String> {
+    let mut result = 0;
+    for ch in s.chars() {
+        match ch {
+            'I' | 'i' | '|' => result += 1,
+            ch if ch.is_whitespace() => (),
+            _ => return Err(format!("invalid character '{}' in unary number", ch)),
+        }
+    }
+    Ok(result)
+}
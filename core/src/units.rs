@@ -3,6 +3,7 @@ use crate::eval::evaluate_to_value;
 use crate::num::Number;
 use crate::scope::GetIdentError;
 use crate::value::Value;
+use std::collections::HashMap;
 
 mod builtin;
 
@@ -15,12 +16,89 @@ pub(crate) enum PrefixRule {
     ShortPrefix,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct UnitDef {
     singular: &'static str,
     plural: &'static str,
     prefix_rule: PrefixRule,
-    value: Value<'static>,
+    value: Number<'static>,
+}
+
+/// Evaluated unit definitions, keyed by the singular and plural names and
+/// the definition of each unit, so that each definition only needs to be
+/// evaluated once per context
+pub(crate) type UnitCache = HashMap<(&'static str, &'static str, &'static str), UnitDef>;
+
+/// Provides exchange rates for currencies, e.g. from a web API, which are
+/// used instead of fend's built-in exchange rates. See
+/// `Context::set_exchange_rate_provider()`.
+pub trait ExchangeRateProvider: Send + Sync {
+    /// Returns how many units of the currency `to` one unit of the currency
+    /// `from` is worth, e.g. `get_rate("USD", "EUR")` might return `0.92`.
+    /// Currencies are given as ISO 4217 codes. If `None` is returned, fend
+    /// falls back to its built-in exchange rate.
+    fn get_rate(&self, from: &str, to: &str) -> Option<f64>;
+}
+
+/// Removes all currencies from the cache, so that their exchange rates
+/// are requested again the next time they are used
+pub(crate) fn clear_cached_currencies(cache: &mut UnitCache) {
+    cache.retain(|_, unit| !unit.value.is_amount_of_money());
+}
+
+// converts an exchange rate like `0.92` to an exact number
+fn rate_to_number<I: Interrupt>(rate: f64, int: &I) -> Result<Number<'static>, IntErr<String, I>> {
+    let mut result = Number::from(0);
+    let mut decimal_places = 0;
+    let mut seen_decimal_point = false;
+    // `f64`'s `Display` implementation never uses exponential notation
+    for ch in rate.to_string().chars() {
+        match ch.to_digit(10) {
+            Some(digit) => {
+                result = result
+                    .mul(10.into(), int)?
+                    .add(u64::from(digit).into(), int)?;
+                if seen_decimal_point {
+                    decimal_places += 1;
+                }
+            }
+            None => seen_decimal_point = true,
+        }
+    }
+    for _ in 0..decimal_places {
+        result = result.div(10.into(), int)?;
+    }
+    Ok(result)
+}
+
+// currencies with a built-in exchange rate like `1.1964 _EUR` use the rate
+// from the context's exchange rate provider instead, if it has one
+fn query_exchange_rate<I: Interrupt>(
+    singular: &'static str,
+    plural: &'static str,
+    definition: &'static str,
+    context: &mut crate::Context,
+    int: &I,
+) -> Result<Option<UnitDef>, IntErr<GetIdentError<'static>, I>> {
+    if !definition.ends_with(" _EUR") {
+        return Ok(None);
+    }
+    let rate = match &context.exchange_rate_provider {
+        Some(provider) => provider.get_rate(singular, "EUR"),
+        None => return Ok(None),
+    };
+    let rate = match rate {
+        Some(rate) if rate.is_finite() && rate > 0.0 => rate,
+        _ => return Ok(None),
+    };
+    let euro = query_unit_internal("EUR", false, true, context, int)?.value;
+    let num = rate_to_number(rate, int)?.mul(euro, int)?;
+    Ok(Some(UnitDef {
+        value: Number::create_unit_value_from_value(&num, "", singular, plural, int)?,
+        prefix_rule: PrefixRule::NoPrefixesAllowed,
+        singular,
+        plural,
+    }))
 }
 
 fn expr_unit<I: Interrupt>(
@@ -50,7 +128,7 @@ fn expr_unit<I: Interrupt>(
     }
     if definition == "!" {
         return Ok(UnitDef {
-            value: Value::Num(Number::new_base_unit(singular, plural)),
+            value: Number::new_base_unit(singular, plural),
             prefix_rule: rule,
             singular,
             plural,
@@ -64,7 +142,7 @@ fn expr_unit<I: Interrupt>(
         num = Number::create_unit_value_from_value(&num, "", singular, plural, int)?;
     }
     Ok(UnitDef {
-        value: Value::Num(num),
+        value: num,
         prefix_rule: rule,
         singular,
         plural,
@@ -75,12 +153,12 @@ fn construct_prefixed_unit<I: Interrupt>(
     a: UnitDef,
     b: UnitDef,
     int: &I,
-) -> Result<Value<'static>, IntErr<String, I>> {
-    let product = a.value.expect_num()?.mul(b.value.expect_num()?, int)?;
+) -> Result<Number<'static>, IntErr<String, I>> {
+    let product = a.value.mul(b.value, int)?;
     assert_eq!(a.singular, a.plural);
     let unit =
         Number::create_unit_value_from_value(&product, a.singular, b.singular, b.plural, int)?;
-    Ok(Value::Num(unit))
+    Ok(unit)
 }
 
 pub(crate) fn query_unit<'a, I: Interrupt>(
@@ -96,19 +174,21 @@ pub(crate) fn query_unit<'a, I: Interrupt>(
     query_unit_static(ident, context, int)
 }
 
-pub(crate) fn query_unit_static<'a, I: Interrupt>(
+pub(crate) fn query_unit_static<'a, 'b, I: Interrupt>(
     ident: &'a str,
     context: &mut crate::Context,
     int: &I,
-) -> Result<Value<'static>, IntErr<GetIdentError<'a>, I>> {
+) -> Result<Value<'b>, IntErr<GetIdentError<'a>, I>> {
     match query_unit_case_sensitive(ident, true, context, int) {
         Err(IntErr::Error(GetIdentError::IdentifierNotFound(_))) => (),
         Err(e) => return Err(e),
         Ok(value) => {
-            return Ok(value);
+            return Ok(Value::Num(value));
         }
     }
-    query_unit_case_sensitive(ident, false, context, int)
+    Ok(Value::Num(query_unit_case_sensitive(
+        ident, false, context, int,
+    )?))
 }
 
 fn query_unit_case_sensitive<'a, I: Interrupt>(
@@ -116,7 +196,7 @@ fn query_unit_case_sensitive<'a, I: Interrupt>(
     case_sensitive: bool,
     context: &mut crate::Context,
     int: &I,
-) -> Result<Value<'static>, IntErr<GetIdentError<'a>, I>> {
+) -> Result<Number<'static>, IntErr<GetIdentError<'a>, I>> {
     match query_unit_internal(ident, false, case_sensitive, context, int) {
         Err(IntErr::Error(GetIdentError::IdentifierNotFound(_))) => (),
         Err(e) => return Err(e),
@@ -158,6 +238,10 @@ fn query_unit_case_sensitive<'a, I: Interrupt>(
     Err(GetIdentError::IdentifierNotFound(ident).into())
 }
 
+pub(crate) fn query_description(ident: &str) -> Option<(&'static str, &'static str, &'static str)> {
+    builtin::query_description(ident)
+}
+
 fn query_unit_internal<'a, I: Interrupt>(
     ident: &'a str,
     short_prefixes: bool,
@@ -166,7 +250,15 @@ fn query_unit_internal<'a, I: Interrupt>(
     int: &I,
 ) -> Result<UnitDef, IntErr<GetIdentError<'a>, I>> {
     if let Some((s, p, expr)) = builtin::query_unit(ident, short_prefixes, case_sensitive) {
-        expr_unit(s, p, expr, context, int)
+        if let Some(unit) = context.unit_cache.get(&(s, p, expr)) {
+            return Ok(unit.clone());
+        }
+        let unit = match query_exchange_rate(s, p, expr, context, int)? {
+            Some(unit) => unit,
+            None => expr_unit(s, p, expr, context, int)?,
+        };
+        context.unit_cache.insert((s, p, expr), unit.clone());
+        Ok(unit)
     } else {
         Err(GetIdentError::IdentifierNotFound(ident).into())
     }
@@ -211,6 +211,67 @@ const CONSTANTS: &[UnitTuple] = &[
         "size of a mole (exact)",
     ),
     ("N_A", "", "=avogadro", ""),
+    (
+        "gas_constant",
+        "",
+        "=avogadro boltzmann",
+        "molar gas constant (exact)",
+    ),
+    ("R_gas", "", "=gas_constant", ""),
+    (
+        "stefan_boltzmann",
+        "",
+        "=5.670374419e-8 W m^-2 K^-4",
+        "Stefan-Boltzmann constant",
+    ),
+    (
+        "faraday",
+        "",
+        "=avogadro electron_charge",
+        "Faraday constant (exact)",
+    ),
+    (
+        "mu_0",
+        "",
+        "=4e-7 pi N / A^2",
+        "magnetic permeability of vacuum",
+    ),
+    (
+        "epsilon_0",
+        "",
+        "=1 / (mu_0 c^2) to picofarad / m",
+        "electric permittivity of vacuum",
+    ),
+    (
+        "fine_structure_constant",
+        "",
+        "=approx. 0.0072973525693",
+        "fine-structure constant",
+    ),
+    ("alpha", "", "=fine_structure_constant", ""),
+    (
+        "euler_mascheroni",
+        "",
+        "=approx. 0.57721566490153286061",
+        "Euler-Mascheroni constant",
+    ),
+    (
+        "apery_constant",
+        "",
+        "=approx. 1.20205690315959428540",
+        "Apery's constant, i.e. zeta(3)",
+    ),
+    (
+        "catalan_constant",
+        "",
+        "=approx. 0.91596559417721901505",
+        "Catalan's constant",
+    ),
+    // Greek letters that commonly refer to one of the constants above
+    ("gamma", "", "=euler_mascheroni", ""),
+    ("sigma", "", "=stefan_boltzmann", ""),
+    ("epsilon", "", "=epsilon_0", ""),
+    ("mu", "", "=mu_0", ""),
     (
         "G",
         "",
@@ -224,28 +285,30 @@ const CONSTANTS: &[UnitTuple] = &[
 const ANGLES: &[UnitTuple] = &[
     ("radian", "radians", "l@1", ""),
     ("circle", "circles", "l@2 pi radian", ""),
-    ("degree", "degrees", "l@1/360 circle", ""),
+    ("degree", "degrees", "l@circle/360", ""),
     ("deg", "degs", "l@degree", ""),
     ("\u{b0}", "", "degree", ""), // degree symbol
     ("arcdeg", "arcdegs", "degree", ""),
-    ("arcmin", "arcmins", "l@1/60 degree", ""),
+    ("arcmin", "arcmins", "l@degree/60", ""),
     ("arcminute", "arcminutes", "l@arcmin", ""),
-    ("arcsec", "arcsecs", "l@1/60 arcmin", ""),
+    ("arcsec", "arcsecs", "l@arcmin/60", ""),
     ("arcsecond", "arcseconds", "l@arcsec", ""),
+    ("\u{2032}", "", "arcmin", ""), // prime symbol
+    ("\u{2033}", "", "arcsec", ""), // double prime symbol
     ("rightangle", "rightangles", "l@90 degrees", ""),
-    ("quadrant", "quadrants", "l@1/4 circle", ""),
-    ("quintant", "quintants", "l@1/5 circle", ""),
-    ("sextant", "sextants", "l@1/6 circle", ""),
+    ("quadrant", "quadrants", "l@circle/4", ""),
+    ("quintant", "quintants", "l@circle/5", ""),
+    ("sextant", "sextants", "l@circle/6", ""),
     (
         "zodiac_sign",
         "zodiac_signs",
-        "l@1/12 circle",
+        "l@circle/12",
         "Angular extent of one sign of the zodiac",
     ),
     ("turn", "turns", "l@circle", ""),
     ("revolution", "revolutions", "l@circle", ""),
     ("rev", "revs", "l@circle", ""),
-    ("gradian", "gradians", "l@1/100 rightangle", ""),
+    ("gradian", "gradians", "l@rightangle/100", ""),
     ("gon", "gons", "l@gradian", ""),
     ("grad", "", "l@gradian", ""),
     ("mas", "", "milliarcsec", ""),
@@ -290,7 +353,7 @@ const COMMON_SI_DERIVED_UNITS: &[UnitTuple] = &[
     ("ohm", "ohms", "l@V/A", "electrical resistance"),
     ("siemens", "", "l@A/V", "electrical conductance"),
     ("S", "", "s@siemens", ""),
-    ("farad", "", "l@C/V", "capacitance"),
+    ("farad", "", "l@coulomb/V", "capacitance"),
     ("F", "", "s@farad", ""),
     ("weber", "", "l@V s", "magnetic flux"),
     ("Wb", "", "s@weber", ""),
@@ -426,6 +489,13 @@ const COMMON_PHYSICAL_UNITS: &[UnitTuple] = &[
     ("btu", "", "british_thermal_unit", ""),
     ("Wh", "", "s@W hour", ""),
     ("bar", "", "l@1e5 Pa", "about 1 atmosphere"),
+    (
+        "atmosphere",
+        "atmospheres",
+        "l@101325 Pa",
+        "standard atmosphere",
+    ),
+    ("atm", "", "s@atmosphere", ""),
     ("diopter", "", "l@/m", "reciprocal of focal length"),
     // TODO remove these compatibility units
     ("lightyear", "lightyears", "light_year", ""),
@@ -681,6 +751,19 @@ pub(crate) fn query_unit<'a>(
     None
 }
 
+/// Returns the singular name, definition and description of a unit,
+/// matching the identifier exactly.
+pub(crate) fn query_description(ident: &str) -> Option<(&'static str, &'static str, &'static str)> {
+    for group in ALL_UNIT_DEFS {
+        for (singular, plural, definition, description) in *group {
+            if *singular == ident || *plural == ident {
+                return Some((singular, definition, description));
+            }
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
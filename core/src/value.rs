@@ -1,9 +1,11 @@
 use crate::error::{IntErr, Interrupt};
-use crate::num::{Base, FormattingStyle, Number};
+use crate::num::{Base, FormattingStyle, Measurement, Number};
 use crate::scope::Scope;
 use crate::{ast::Expr, ident::Ident};
 use crate::{Span, SpanKind};
-use std::{borrow, fmt, sync::Arc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::{borrow, cmp::Ordering, fmt};
 
 #[derive(Clone)]
 pub(crate) enum Value<'a> {
@@ -12,14 +14,24 @@ pub(crate) enum Value<'a> {
     Format(FormattingStyle),
     Dp,
     Sf,
+    // placeholder `?` for the value that `ideal_gas` should solve for
+    Unknown,
     Base(Base),
     // user-defined function with a named parameter
     Fn(Ident<'a>, Box<Expr<'a>>, Option<Arc<Scope<'a>>>),
     Object(Vec<(&'a str, Box<Value<'a>>)>),
     String(borrow::Cow<'a, str>),
     Date(crate::date::Date),
+    List(Vec<Value<'a>>),
+    Measurement(Box<Measurement<'a>>),
+    // function returned by `memo`, which caches its results by argument
+    Memo(Box<Value<'a>>, MemoCache<'a>),
 }
 
+// results of a memoised function, keyed by `Value::cache_key` of the
+// arguments
+pub(crate) type MemoCache<'a> = Arc<Mutex<HashMap<String, Value<'a>>>>;
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub(crate) enum BuiltInFunction {
     Approximately,
@@ -42,6 +54,96 @@ pub(crate) enum BuiltInFunction {
     Base,
     Differentiate,
     Conjugate,
+    SeriesE,
+    Help,
+    ToFixed,
+    ToPrecision,
+    NumDigits,
+    ReverseDigits,
+    ToRoman,
+    FromRoman,
+    Bcd,
+    FromBcd,
+    Interleave,
+    FromNato,
+    HexDump,
+    HexToString,
+    Crc32,
+    Crc16,
+    Djb2,
+    Fnv1a,
+    UnixTime,
+    FromUnixTime,
+    LunarPhase,
+    Season,
+    TimeZoneOffset,
+    Countdown,
+    Age,
+    FormatNumber,
+    Nth,
+    Add,
+    Zip,
+    Map,
+    Filter,
+    Fold,
+    Range,
+    Sum,
+    Tabulate,
+    Histogram,
+    Mean,
+    Median,
+    Variance,
+    VarianceSample,
+    Stddev,
+    StddevSample,
+    FormatWithUncertainty,
+    PopCount,
+    CountLeadingZeros,
+    SwapBytes,
+    RotateLeft,
+    RotateRight,
+    GrayCode,
+    FromGray,
+    HammingDistance,
+    ToUnary,
+    FromUnary,
+    ToBrailleNumber,
+    FromBrailleNumber,
+    LcmRange,
+    Primorial,
+    Collatz,
+    TaxiDistance,
+    ChebyshevDistance,
+    Haversine,
+    CompassBearing,
+    DecibelsToPowerRatio,
+    PowerRatioToDecibels,
+    DecibelsToAmplitudeRatio,
+    AmplitudeRatioToDecibels,
+    VswrToReflection,
+    ReflectionToVswr,
+    OhmsLaw,
+    Parallel,
+    RcTimeConstant,
+    LcResonance,
+    Snell,
+    IdealGas,
+    OrdinalWords,
+    Memo,
+    IntegerPart,
+    FractionalPart,
+    ToBalancedTernary,
+    IsLeapYear,
+    DaysInYear,
+    Easter,
+    EasterOrthodox,
+    ConvertCurrency,
+    Exponent,
+    Mantissa,
+    ApplyAt,
+    Compose,
+    Convergents,
+    Bernstein,
 }
 
 impl BuiltInFunction {
@@ -60,7 +162,7 @@ impl BuiltInFunction {
         )
     }
 
-    pub(crate) fn invert(self) -> Result<Value<'static>, String> {
+    pub(crate) fn invert<'a>(self) -> Result<Value<'a>, String> {
         Ok(match self {
             Self::Sin => Value::BuiltInFunction(Self::Asin),
             Self::Cos => Value::BuiltInFunction(Self::Acos),
@@ -78,6 +180,7 @@ impl BuiltInFunction {
         })
     }
 
+    #[allow(clippy::too_many_lines)]
     const fn as_str(self) -> &'static str {
         match self {
             Self::Approximately => "approximately",
@@ -100,10 +203,100 @@ impl BuiltInFunction {
             Self::Base => "base",
             Self::Differentiate => "differentiate",
             Self::Conjugate => "conjugate",
+            Self::SeriesE => "series_e",
+            Self::Help => "help",
+            Self::ToFixed => "to_fixed",
+            Self::ToPrecision => "to_precision",
+            Self::NumDigits => "num_digits",
+            Self::ReverseDigits => "reverse_digits",
+            Self::ToRoman => "to_roman",
+            Self::FromRoman => "from_roman",
+            Self::Bcd => "bcd",
+            Self::FromBcd => "from_bcd",
+            Self::Interleave => "interleave",
+            Self::FromNato => "from_nato",
+            Self::HexDump => "hex_dump",
+            Self::HexToString => "hex_to_string",
+            Self::Crc32 => "crc32",
+            Self::Crc16 => "crc16",
+            Self::Djb2 => "djb2",
+            Self::Fnv1a => "fnv1a",
+            Self::UnixTime => "unix_time",
+            Self::FromUnixTime => "from_unix_time",
+            Self::LunarPhase => "lunar_phase",
+            Self::Season => "season",
+            Self::TimeZoneOffset => "time_zone_offset",
+            Self::Countdown => "countdown",
+            Self::Age => "age",
+            Self::FormatNumber => "format_number",
+            Self::Nth => "nth",
+            Self::Add => "add",
+            Self::Zip => "zip",
+            Self::Map => "map",
+            Self::Filter => "filter",
+            Self::Fold => "fold",
+            Self::Range => "range",
+            Self::Sum => "sum",
+            Self::Tabulate => "tabulate",
+            Self::Histogram => "histogram",
+            Self::Mean => "mean",
+            Self::Median => "median",
+            Self::Variance => "variance",
+            Self::VarianceSample => "variance_sample",
+            Self::Stddev => "stddev",
+            Self::StddevSample => "stddev_sample",
+            Self::FormatWithUncertainty => "format_with_uncertainty",
+            Self::PopCount => "bitcount",
+            Self::CountLeadingZeros => "clz",
+            Self::SwapBytes => "swap_bytes",
+            Self::RotateLeft => "rotate_left",
+            Self::RotateRight => "rotate_right",
+            Self::GrayCode => "gray_code",
+            Self::FromGray => "from_gray",
+            Self::HammingDistance => "hamming_distance",
+            Self::ToUnary => "to_unary",
+            Self::FromUnary => "from_unary",
+            Self::ToBrailleNumber => "to_braille_number",
+            Self::FromBrailleNumber => "from_braille_number",
+            Self::LcmRange => "lcm_range",
+            Self::Primorial => "primorial",
+            Self::Collatz => "collatz",
+            Self::TaxiDistance => "taxi_distance",
+            Self::ChebyshevDistance => "chebyshev_distance",
+            Self::Haversine => "haversine",
+            Self::CompassBearing => "compass_bearing",
+            Self::DecibelsToPowerRatio => "dB_to_power_ratio",
+            Self::PowerRatioToDecibels => "power_ratio_to_dB",
+            Self::DecibelsToAmplitudeRatio => "dB_to_amplitude_ratio",
+            Self::AmplitudeRatioToDecibels => "amplitude_ratio_to_dB",
+            Self::VswrToReflection => "VSWR_to_reflection",
+            Self::ReflectionToVswr => "reflection_to_VSWR",
+            Self::OhmsLaw => "ohms_law",
+            Self::Parallel => "parallel",
+            Self::RcTimeConstant => "rc_time_constant",
+            Self::LcResonance => "lc_resonance",
+            Self::Snell => "snell",
+            Self::IdealGas => "ideal_gas",
+            Self::OrdinalWords => "ordinal_words",
+            Self::Memo => "memo",
+            Self::IntegerPart => "integer_part",
+            Self::FractionalPart => "fractional_part",
+            Self::ToBalancedTernary => "to_balanced_ternary",
+            Self::IsLeapYear => "is_leap_year",
+            Self::DaysInYear => "days_in_year",
+            Self::Easter => "easter",
+            Self::EasterOrthodox => "easter_orthodox",
+            Self::ConvertCurrency => "convert_currency",
+            Self::Exponent => "exponent",
+            Self::Mantissa => "mantissa",
+            Self::ApplyAt => "apply_at",
+            Self::Compose => "compose",
+            Self::Convergents => "convergents",
+            Self::Bernstein => "bernstein",
         }
     }
 
-    fn differentiate(self) -> Option<Value<'static>> {
+    fn differentiate<'a>(self) -> Option<Value<'a>> {
         if self == Self::Sin {
             Some(Value::BuiltInFunction(Self::Cos))
         } else {
@@ -128,10 +321,83 @@ impl<'a> Value<'a> {
     pub(crate) fn expect_num<I: Interrupt>(self) -> Result<Number<'a>, IntErr<String, I>> {
         match self {
             Self::Num(bigrat) => Ok(bigrat),
+            Self::Measurement(_) => Err(
+                "this operation is not supported for numbers with an uncertainty"
+                    .to_string()
+                    .into(),
+            ),
+            _ => Err("expected a number".to_string().into()),
+        }
+    }
+
+    pub(crate) fn expect_string<I: Interrupt>(
+        self,
+    ) -> Result<borrow::Cow<'a, str>, IntErr<String, I>> {
+        match self {
+            Self::String(s) => Ok(s),
+            _ => Err("expected a string".to_string().into()),
+        }
+    }
+
+    pub(crate) fn expect_date<I: Interrupt>(self) -> Result<crate::date::Date, IntErr<String, I>> {
+        match self {
+            Self::Date(d) => Ok(d),
+            _ => Err("expected a date".to_string().into()),
+        }
+    }
+
+    /// Sets the formatting style of a number or a measurement
+    pub(crate) fn try_with_format<I: Interrupt>(
+        self,
+        format: FormattingStyle,
+    ) -> Result<Self, IntErr<String, I>> {
+        Ok(match self {
+            Self::Measurement(m) => Self::Measurement(Box::new(m.try_with_format(format)?)),
+            value => Self::Num(value.expect_num()?.try_with_format(format)?),
+        })
+    }
+
+    /// Sets the base of a number or a measurement
+    pub(crate) fn with_base<I: Interrupt>(self, base: Base) -> Result<Self, IntErr<String, I>> {
+        Ok(match self {
+            Self::Measurement(m) => Self::Measurement(Box::new(m.with_base(base))),
+            value => Self::Num(value.expect_num()?.with_base(base)),
+        })
+    }
+
+    /// Converts numbers into measurements without any uncertainty.
+    pub(crate) fn expect_measurement<I: Interrupt>(
+        self,
+        int: &I,
+    ) -> Result<Measurement<'a>, IntErr<String, I>> {
+        match self {
+            Self::Measurement(m) => Ok(*m),
+            Self::Num(n) => Measurement::exact(n, int),
             _ => Err("expected a number".to_string().into()),
         }
     }
 
+    pub(crate) fn expect_list<I: Interrupt>(self) -> Result<Vec<Self>, IntErr<String, I>> {
+        match self {
+            Self::List(items) => Ok(items),
+            _ => Err("expected a list".to_string().into()),
+        }
+    }
+
+    /// Splits a list of two function arguments into its elements.
+    pub(crate) fn expect_two_args<I: Interrupt>(self) -> Result<(Self, Self), IntErr<String, I>> {
+        match self {
+            Self::List(args) if args.len() == 2 => {
+                let mut args = args.into_iter();
+                match (args.next(), args.next()) {
+                    (Some(a), Some(b)) => Ok((a, b)),
+                    _ => unreachable!(),
+                }
+            }
+            _ => Err("expected 2 arguments".to_string().into()),
+        }
+    }
+
     pub(crate) fn handle_num<I: Interrupt>(
         self,
         eval_fn: impl FnOnce(Number<'a>) -> Result<Number<'a>, IntErr<String, I>>,
@@ -168,6 +434,13 @@ impl<'a> Value<'a> {
             (Self::Num(a), Self::Fn(param, expr, scope)) => {
                 Self::Fn(param, Box::new(lazy_fn_rhs(a)(expr)), scope)
             }
+            (Self::Measurement(_), _) | (_, Self::Measurement(_)) => {
+                return Err(
+                    "this operation is not supported for numbers with an uncertainty"
+                        .to_string()
+                        .into(),
+                )
+            }
             _ => return Err("expected a number".to_string().into()),
         })
     }
@@ -211,6 +484,11 @@ impl<'a> Value<'a> {
                     )
                     .into());
                 }
+                if let Self::Measurement(m) = other {
+                    return Ok(Self::Measurement(Box::new(
+                        Measurement::exact(n, int)?.mul(*m, int)?,
+                    )));
+                }
                 let n2 = n.clone();
                 other.handle_num(
                     |x| n.mul(x, int).map_err(IntErr::into_string),
@@ -222,9 +500,15 @@ impl<'a> Value<'a> {
                 Self::apply_built_in_function(func, other, scope, context, int)?
             }
             Self::Fn(param, expr, custom_scope) => {
-                let new_scope =
-                    Scope::with_variable(param.as_str(), other, scope.clone(), custom_scope);
-                return crate::ast::evaluate(*expr, Some(Arc::new(new_scope)), context, int);
+                Self::apply_fn(param, *expr, custom_scope, other, scope, context, int)?
+            }
+            Self::Memo(func, cache) => {
+                Self::apply_memo(*func, &cache, other, scope.as_ref(), context, int)?
+            }
+            // e.g. `(10 ± 1) m`, which scales both the value and the uncertainty
+            Self::Measurement(m) if apply_mul_handling == ApplyMulHandling::Both => {
+                let other = crate::ast::evaluate(other, scope, context, int)?;
+                Self::Measurement(Box::new(m.mul(other.expect_measurement(int)?, int)?))
             }
             _ => {
                 return Err(format!(
@@ -236,6 +520,200 @@ impl<'a> Value<'a> {
         })
     }
 
+    fn is_function(&self) -> bool {
+        matches!(
+            self,
+            Self::BuiltInFunction(_) | Self::Fn(_, _, _) | Self::Memo(_, _)
+        )
+    }
+
+    /// Returns the composition `self ∘ inner`, i.e. a function that
+    /// applies `inner` first and then `self` to the result.
+    pub(crate) fn compose(self, inner: Self) -> Result<Self, String> {
+        if !self.is_function() || !inner.is_function() {
+            return Err("only functions can be composed".to_string());
+        }
+        let scope = Scope::with_value(
+            "f",
+            self,
+            Some(Arc::new(Scope::with_value("g", inner, None))),
+        );
+        Ok(Self::Fn(
+            Ident::new("x"),
+            Box::new(Expr::ApplyFunctionCall(
+                Box::new(Expr::Ident(Ident::new("f"))),
+                Box::new(Expr::ApplyFunctionCall(
+                    Box::new(Expr::Ident(Ident::new("g"))),
+                    Box::new(Expr::Ident(Ident::new("x"))),
+                )),
+            )),
+            Some(Arc::new(scope)),
+        ))
+    }
+
+    // implements `add(a, b)`
+    fn add_args<I: Interrupt>(arg: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let (a, b) = arg.expect_two_args()?;
+        Ok(Self::Num(a.expect_num()?.add(b.expect_num()?, int)?))
+    }
+
+    // implements `compose(f, g)`
+    fn compose_args<I: Interrupt>(arg: Self) -> Result<Self, IntErr<String, I>> {
+        let (outer, inner) = arg.expect_two_args()?;
+        Ok(outer.compose(inner)?)
+    }
+
+    /// Applies a function to arguments that have already been evaluated,
+    /// e.g. to the elements of a list in `zip`.
+    pub(crate) fn apply_to_values<I: Interrupt>(
+        self,
+        args: Vec<Self>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        // these names can't be written by the user, so they never shadow
+        // any variables used in the function body
+        const ARG_NAMES: [&str; 2] = ["%arg0", "%arg1"];
+        if args.is_empty() || args.len() > ARG_NAMES.len() {
+            return Err(format!("cannot apply a function to {} arguments", args.len()).into());
+        }
+        let mut scope = None;
+        let mut arg_exprs = vec![];
+        for (name, value) in ARG_NAMES.iter().zip(args) {
+            scope = Some(Arc::new(Scope::with_value(name, value, scope)));
+            arg_exprs.push(Expr::Ident(Ident::new(name)));
+        }
+        let arg = if arg_exprs.len() == 1 {
+            arg_exprs.remove(0)
+        } else {
+            Expr::Parens(Box::new(Expr::List(arg_exprs)))
+        };
+        self.apply(arg, ApplyMulHandling::OnlyApply, scope, context, int)
+    }
+
+    // Applies a lambda to its arguments. Functions with multiple parameters
+    // are curried, so `f(a, b)` is evaluated as `(f a) b`. If the body of
+    // the lambda is a call to another lambda, that call is evaluated in the
+    // same loop instead of recursively, so that long chains of tail calls
+    // don't overflow the stack.
+    fn apply_fn<I: Interrupt>(
+        mut param: Ident<'a>,
+        mut body: Expr<'a>,
+        mut custom_scope: Option<Arc<Scope<'a>>>,
+        arg: Expr<'a>,
+        scope: Option<Arc<Scope<'a>>>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        // arguments that still need to be applied, in reverse order
+        let mut pending_args = vec![];
+        push_args(arg, scope, &mut pending_args);
+        let (mut arg, mut arg_scope) = pending_args.pop().expect("argument list is never empty");
+        let mut result = loop {
+            crate::interrupt::test_int(int).map_err(IntErr::into_string)?;
+            let body_scope = Some(Arc::new(Scope::with_variable(
+                param.as_str(),
+                arg,
+                arg_scope,
+                custom_scope,
+            )));
+            let (func, next_arg, apply_mul_handling) = match body {
+                Expr::ApplyFunctionCall(a, b) => (a, b, ApplyMulHandling::OnlyApply),
+                Expr::Apply(a, b) | Expr::ApplyMul(a, b) => {
+                    if let (Expr::Ident(a), Expr::Ident(b)) = (&*a, &*b) {
+                        let ident = format!("{}_{}", a, b);
+                        if let Ok(val) = crate::units::query_unit_static(&ident, context, int) {
+                            break val;
+                        }
+                    }
+                    (a, b, ApplyMulHandling::Both)
+                }
+                body => break crate::ast::evaluate(body, body_scope, context, int)?,
+            };
+            match crate::ast::evaluate(*func, body_scope.clone(), context, int)? {
+                Self::Fn(next_param, next_body, next_custom_scope) => {
+                    push_args(*next_arg, body_scope, &mut pending_args);
+                    let (next_arg, next_arg_scope) =
+                        pending_args.pop().expect("argument list is never empty");
+                    param = next_param;
+                    body = *next_body;
+                    custom_scope = next_custom_scope;
+                    arg = next_arg;
+                    arg_scope = next_arg_scope;
+                }
+                func => {
+                    break func.apply(*next_arg, apply_mul_handling, body_scope, context, int)?;
+                }
+            }
+        };
+        while let Some((arg, arg_scope)) = pending_args.pop() {
+            result = result.apply(arg, ApplyMulHandling::OnlyApply, arg_scope, context, int)?;
+        }
+        Ok(result)
+    }
+
+    // implements `memo(f)`
+    fn memo<I: Interrupt>(arg: Self) -> Result<Self, IntErr<String, I>> {
+        match arg {
+            memo @ Self::Memo(_, _) => Ok(memo),
+            func @ (Self::BuiltInFunction(_) | Self::Fn(_, _, _)) => {
+                Ok(Self::Memo(Box::new(func), MemoCache::default()))
+            }
+            _ => Err("memo expects a function".to_string().into()),
+        }
+    }
+
+    fn apply_memo<I: Interrupt>(
+        func: Self,
+        cache: &MemoCache<'a>,
+        arg: Expr<'a>,
+        scope: Option<&Arc<Scope<'a>>>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let args = match arg {
+            Expr::Parens(inner) => match *inner {
+                Expr::List(items) => items,
+                inner => vec![inner],
+            },
+            arg => vec![arg],
+        };
+        let mut values = vec![];
+        for arg in args {
+            values.push(crate::ast::evaluate(arg, scope.cloned(), context, int)?);
+        }
+        let mut keys = vec![];
+        for value in &values {
+            keys.push(value.cache_key(int)?);
+        }
+        let key = format!("{:?}", keys);
+        if let Some(result) = cache.lock().expect("memo cache is poisoned").get(&key) {
+            return Ok(result.clone());
+        }
+        let result = func.apply_to_values(values, context, int)?;
+        cache
+            .lock()
+            .expect("memo cache is poisoned")
+            .insert(key, result.clone());
+        Ok(result)
+    }
+
+    // numbers are keyed by their exact value, since their internal
+    // representation (and therefore their debug output) is not unique
+    fn cache_key<I: Interrupt>(&self, int: &I) -> Result<String, IntErr<String, I>> {
+        Ok(match self {
+            Self::Num(n) => n.cache_key(int)?,
+            Self::List(items) => {
+                let mut keys = vec![];
+                for item in items {
+                    keys.push(item.cache_key(int)?);
+                }
+                format!("{:?}", keys)
+            }
+            _ => format!("{:?}", self),
+        })
+    }
+
     fn apply_built_in_function<I: Interrupt>(
         func: BuiltInFunction,
         arg: Expr<'a>,
@@ -243,42 +721,1248 @@ impl<'a> Value<'a> {
         context: &mut crate::Context,
         int: &I,
     ) -> Result<Self, IntErr<String, I>> {
+        if func == BuiltInFunction::Help {
+            return Self::help(arg);
+        }
         let arg = crate::ast::evaluate(arg, scope.clone(), context, int)?;
-        Ok(Self::Num(match func {
-            BuiltInFunction::Approximately => arg.expect_num()?.make_approximate(),
-            BuiltInFunction::Abs => arg.expect_num()?.abs(int)?,
-            BuiltInFunction::Sin => arg.expect_num()?.sin(scope, context, int)?,
-            BuiltInFunction::Cos => arg.expect_num()?.cos(scope, context, int)?,
-            BuiltInFunction::Tan => arg.expect_num()?.tan(scope, context, int)?,
-            BuiltInFunction::Asin => arg.expect_num()?.asin(int)?,
-            BuiltInFunction::Acos => arg.expect_num()?.acos(int)?,
-            BuiltInFunction::Atan => arg.expect_num()?.atan(int)?,
-            BuiltInFunction::Sinh => arg.expect_num()?.sinh(int)?,
-            BuiltInFunction::Cosh => arg.expect_num()?.cosh(int)?,
-            BuiltInFunction::Tanh => arg.expect_num()?.tanh(int)?,
-            BuiltInFunction::Asinh => arg.expect_num()?.asinh(int)?,
-            BuiltInFunction::Acosh => arg.expect_num()?.acosh(int)?,
-            BuiltInFunction::Atanh => arg.expect_num()?.atanh(int)?,
-            BuiltInFunction::Ln => arg.expect_num()?.ln(int)?,
-            BuiltInFunction::Log2 => arg.expect_num()?.log2(int)?,
-            BuiltInFunction::Log10 => arg.expect_num()?.log10(int)?,
-            BuiltInFunction::Base => {
-                use std::convert::TryInto;
-                let n: u8 = arg
+        match func {
+            BuiltInFunction::Base => Self::base_from_value(arg, int),
+            BuiltInFunction::Differentiate => arg.differentiate("x", int),
+            BuiltInFunction::ToFixed | BuiltInFunction::ToPrecision => {
+                Self::format_with_digits(func, arg, int)
+            }
+            BuiltInFunction::NumDigits
+            | BuiltInFunction::ReverseDigits
+            | BuiltInFunction::ToRoman
+            | BuiltInFunction::FromRoman
+            | BuiltInFunction::ToBalancedTernary
+            | BuiltInFunction::Interleave
+            | BuiltInFunction::PopCount
+            | BuiltInFunction::GrayCode
+            | BuiltInFunction::FromGray
+            | BuiltInFunction::HammingDistance
+            | BuiltInFunction::ToUnary
+            | BuiltInFunction::FromUnary
+            | BuiltInFunction::ToBrailleNumber
+            | BuiltInFunction::FromBrailleNumber
+            | BuiltInFunction::LcmRange
+            | BuiltInFunction::Primorial
+            | BuiltInFunction::Collatz
+            | BuiltInFunction::OrdinalWords => Self::apply_integer_function(func, arg, int),
+            BuiltInFunction::CountLeadingZeros | BuiltInFunction::SwapBytes => {
+                Self::apply_bit_function(func, arg, int)
+            }
+            BuiltInFunction::RotateLeft | BuiltInFunction::RotateRight => {
+                Self::rotate(func, arg, int)
+            }
+            BuiltInFunction::Exponent | BuiltInFunction::Mantissa => {
+                Self::exponent_or_mantissa(func, arg, int)
+            }
+            BuiltInFunction::FromNato
+            | BuiltInFunction::HexDump
+            | BuiltInFunction::HexToString
+            | BuiltInFunction::Crc32
+            | BuiltInFunction::Crc16
+            | BuiltInFunction::Djb2
+            | BuiltInFunction::Fnv1a => Self::apply_string_function(func, arg, int),
+            BuiltInFunction::UnixTime
+            | BuiltInFunction::FromUnixTime
+            | BuiltInFunction::LunarPhase
+            | BuiltInFunction::Season
+            | BuiltInFunction::IsLeapYear
+            | BuiltInFunction::DaysInYear
+            | BuiltInFunction::Easter
+            | BuiltInFunction::EasterOrthodox => Self::apply_date_function(func, arg, int),
+            BuiltInFunction::TimeZoneOffset => Self::time_zone_offset(arg, scope, context, int),
+            BuiltInFunction::Countdown => Self::countdown(arg, scope, context, int),
+            BuiltInFunction::Age => Self::age(arg, context),
+            BuiltInFunction::FormatNumber => Self::format_number(arg, int),
+            BuiltInFunction::Nth => Self::nth(arg, int),
+            BuiltInFunction::Add => Self::add_args(arg, int),
+            BuiltInFunction::ConvertCurrency => Self::convert_currency(arg, int),
+            BuiltInFunction::Zip => Self::zip(arg, context, int),
+            BuiltInFunction::Memo => Self::memo(arg),
+            BuiltInFunction::Map => Self::map(arg, context, int),
+            BuiltInFunction::Filter => Self::filter(arg, context, int),
+            BuiltInFunction::Fold => Self::fold(arg, context, int),
+            BuiltInFunction::ApplyAt => Self::apply_at(arg, context, int),
+            BuiltInFunction::Compose => Self::compose_args(arg),
+            BuiltInFunction::Convergents => Self::convergents(arg, int),
+            BuiltInFunction::Bernstein => Self::bernstein(arg, int),
+            BuiltInFunction::Range => Self::range(arg, false, int),
+            BuiltInFunction::Sum => Self::sum(arg, int),
+            BuiltInFunction::Tabulate => Self::tabulate(arg, context, int),
+            BuiltInFunction::Histogram => Self::histogram(arg, int),
+            BuiltInFunction::Mean
+            | BuiltInFunction::Median
+            | BuiltInFunction::Variance
+            | BuiltInFunction::VarianceSample
+            | BuiltInFunction::Stddev
+            | BuiltInFunction::StddevSample => Self::apply_statistics_function(func, arg, int),
+            BuiltInFunction::TaxiDistance | BuiltInFunction::ChebyshevDistance => {
+                Self::grid_distance(func, arg, int)
+            }
+            BuiltInFunction::Haversine => Self::haversine(arg, scope, context, int),
+            BuiltInFunction::OhmsLaw => Self::ohms_law(arg, scope, context, int),
+            BuiltInFunction::Parallel => Self::parallel(arg, int),
+            BuiltInFunction::Snell => Self::snell(arg, scope, context, int),
+            BuiltInFunction::IdealGas => Self::ideal_gas(arg, scope, context, int),
+            BuiltInFunction::RcTimeConstant | BuiltInFunction::LcResonance => {
+                Self::apply_circuit_function(func, arg, scope, context, int)
+            }
+            BuiltInFunction::CompassBearing => Self::compass_bearing(arg, scope, context, int),
+            BuiltInFunction::FormatWithUncertainty => Self::format_with_uncertainty(arg, int),
+            _ => Ok(Self::Num(Self::apply_numeric_function(
+                func,
+                arg.expect_num()?,
+                scope,
+                context,
+                int,
+            )?)),
+        }
+    }
+
+    fn format_with_uncertainty<I: Interrupt>(
+        arg: Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let (x, uncertainty) = arg.expect_two_args()?;
+        let formatted = x
+            .expect_num()?
+            .format_with_uncertainty(uncertainty.expect_num()?, int)?;
+        Ok(Self::String(formatted.into()))
+    }
+
+    // built-in functions that take a single number and return a number
+    fn apply_numeric_function<I: Interrupt>(
+        func: BuiltInFunction,
+        n: Number<'a>,
+        scope: Option<Arc<Scope<'a>>>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Number<'a>, IntErr<String, I>> {
+        match func {
+            BuiltInFunction::Approximately => Ok(n.make_approximate()),
+            BuiltInFunction::Abs => n.abs(int),
+            BuiltInFunction::IntegerPart => n.integer_part(int),
+            BuiltInFunction::FractionalPart => n.fractional_part(int),
+            BuiltInFunction::Sin => n.sin(scope, context, int),
+            BuiltInFunction::Cos => n.cos(scope, context, int),
+            BuiltInFunction::Tan => n.tan(scope, context, int),
+            BuiltInFunction::Asin => n.asin(int),
+            BuiltInFunction::Acos => n.acos(int),
+            BuiltInFunction::Atan => n.atan(int),
+            BuiltInFunction::Sinh => n.sinh(int),
+            BuiltInFunction::Cosh => n.cosh(int),
+            BuiltInFunction::Tanh => n.tanh(int),
+            BuiltInFunction::Asinh => n.asinh(int),
+            BuiltInFunction::Acosh => n.acosh(int),
+            BuiltInFunction::Atanh => n.atanh(int),
+            BuiltInFunction::Ln => n.ln(int),
+            BuiltInFunction::Log2 => n.log2(int),
+            BuiltInFunction::Log10 => n.log10(int),
+            BuiltInFunction::Conjugate => Ok(n.conjugate()),
+            BuiltInFunction::SeriesE => n.series_e(int),
+            BuiltInFunction::Bcd => n.bcd_encode(int),
+            BuiltInFunction::FromBcd => n.bcd_decode(int),
+            BuiltInFunction::DecibelsToPowerRatio => Self::decibels_to_ratio(n, 10, int),
+            BuiltInFunction::PowerRatioToDecibels => Self::ratio_to_decibels(n, 10, int),
+            BuiltInFunction::DecibelsToAmplitudeRatio => Self::decibels_to_ratio(n, 20, int),
+            BuiltInFunction::AmplitudeRatioToDecibels => Self::ratio_to_decibels(n, 20, int),
+            BuiltInFunction::VswrToReflection => Self::vswr_to_reflection(n, int),
+            BuiltInFunction::ReflectionToVswr => Self::reflection_to_vswr(n, int),
+            _ => unreachable!("{} is not a numeric function", func),
+        }
+    }
+
+    // power ratios use a factor of 10, amplitude ratios a factor of 20
+    fn decibels_to_ratio<I: Interrupt>(
+        decibels: Number<'a>,
+        factor: u64,
+        int: &I,
+    ) -> Result<Number<'a>, IntErr<String, I>> {
+        Number::from(10).pow(decibels.div(Number::from(factor), int)?, int)
+    }
+
+    fn ratio_to_decibels<I: Interrupt>(
+        ratio: Number<'a>,
+        factor: u64,
+        int: &I,
+    ) -> Result<Number<'a>, IntErr<String, I>> {
+        if ratio.is_zero() || ratio.is_negative() {
+            return Err("ratio must be positive".to_string().into());
+        }
+        Number::from(factor).mul(ratio.log10(int)?, int)
+    }
+
+    // computes the reflection coefficient `(vswr - 1) / (vswr + 1)`
+    fn vswr_to_reflection<I: Interrupt>(
+        vswr: Number<'a>,
+        int: &I,
+    ) -> Result<Number<'a>, IntErr<String, I>> {
+        if vswr.clone().compare(Number::from(1), int)? == Ordering::Less {
+            return Err("VSWR must be at least 1".to_string().into());
+        }
+        vswr.clone()
+            .sub(Number::from(1), int)?
+            .div(vswr.add(Number::from(1), int)?, int)
+    }
+
+    // computes the VSWR `(1 + |gamma|) / (1 - |gamma|)`
+    fn reflection_to_vswr<I: Interrupt>(
+        gamma: Number<'a>,
+        int: &I,
+    ) -> Result<Number<'a>, IntErr<String, I>> {
+        let gamma = gamma.abs(int)?;
+        if gamma.clone().compare(Number::from(1), int)? != Ordering::Less {
+            return Err("reflection coefficient must be less than 1 in magnitude"
+                .to_string()
+                .into());
+        }
+        Number::from(1)
+            .add(gamma.clone(), int)?
+            .div(Number::from(1).sub(gamma, int)?, int)
+    }
+
+    fn base_from_value<I: Interrupt>(arg: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        use std::convert::TryInto;
+        let n: u8 = arg
+            .expect_num()?
+            .try_as_usize(int)
+            .map_err(IntErr::into_string)?
+            .try_into()
+            .map_err(|_| "unable to convert number to a valid base".to_string())?;
+        Ok(Self::Base(
+            Base::from_plain_base(n).map_err(|e| e.to_string())?,
+        ))
+    }
+
+    // looks up the identifier itself rather than its value
+    fn help<I: Interrupt>(mut arg: Expr<'a>) -> Result<Self, IntErr<String, I>> {
+        while let Expr::Parens(inner) = arg {
+            arg = *inner;
+        }
+        let name = match arg {
+            Expr::Ident(ident) => ident.as_str().to_string(),
+            Expr::String(s) => s.to_string(),
+            _ => return Err("expected the name of a function or unit".to_string().into()),
+        };
+        Ok(Self::String(crate::help::get_help(&name)?.into()))
+    }
+
+    // built-in functions that work on the digits of integers
+    fn apply_integer_function<I: Interrupt>(
+        func: BuiltInFunction,
+        arg: Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        Ok(match func {
+            BuiltInFunction::NumDigits => {
+                let (n, base) = arg.expect_two_args()?;
+                Self::Num(n.expect_num()?.digit_count(base.expect_num()?, int)?)
+            }
+            BuiltInFunction::ReverseDigits => {
+                let (n, base) = arg.expect_two_args()?;
+                Self::Num(n.expect_num()?.reverse_digits(base.expect_num()?, int)?)
+            }
+            BuiltInFunction::ToRoman => {
+                let n = arg
+                    .expect_num()?
+                    .try_as_usize(int)
+                    .map_err(IntErr::into_string)?;
+                let roman = crate::roman::to_roman(n).map_err(|e| e.to_string())?;
+                Self::String(roman.into())
+            }
+            BuiltInFunction::FromRoman => {
+                let n =
+                    crate::roman::from_roman(&arg.expect_string()?).map_err(|e| e.to_string())?;
+                Self::Num(n.into())
+            }
+            BuiltInFunction::ToBalancedTernary => {
+                let n = arg.expect_num()?.try_as_i64(int)?;
+                Self::String(crate::balanced::to_balanced_ternary(n).into())
+            }
+            BuiltInFunction::Interleave => {
+                let (a, b) = arg.expect_two_args()?;
+                Self::Num(a.expect_num()?.interleave_bits(b.expect_num()?, int)?)
+            }
+            BuiltInFunction::PopCount => Self::Num(arg.expect_num()?.count_ones(int)?),
+            BuiltInFunction::LcmRange => Self::Num(arg.expect_num()?.lcm_range(int)?),
+            BuiltInFunction::Primorial => Self::Num(arg.expect_num()?.primorial(int)?),
+            BuiltInFunction::Collatz => Self::Num(arg.expect_num()?.collatz(int)?),
+            BuiltInFunction::OrdinalWords => {
+                let n = arg.expect_num()?.try_as_u64(int)?;
+                Self::String(crate::num::to_ordinal_words(n).into())
+            }
+            BuiltInFunction::GrayCode => Self::Num(
+                arg.expect_num()?
+                    .map_u64(|n| Ok(crate::bits::gray_code(n)), int)?,
+            ),
+            BuiltInFunction::FromGray => Self::Num(
+                arg.expect_num()?
+                    .map_u64(|n| Ok(crate::bits::from_gray(n)), int)?,
+            ),
+            BuiltInFunction::HammingDistance => {
+                let (a, b) = arg.expect_two_args()?;
+                let a = a.expect_num()?.try_as_u64(int)?;
+                let b = b.expect_num()?.try_as_u64(int)?;
+                Self::Num(Number::from(u64::from((a ^ b).count_ones())))
+            }
+            BuiltInFunction::ToUnary => {
+                let n = arg
                     .expect_num()?
                     .try_as_usize(int)
-                    .map_err(IntErr::into_string)?
-                    .try_into()
-                    .map_err(|_| "unable to convert number to a valid base".to_string())?;
-                return Ok(Self::Base(
-                    Base::from_plain_base(n).map_err(|e| e.to_string())?,
-                ));
-            }
-            BuiltInFunction::Differentiate => return arg.differentiate("x", int),
-            BuiltInFunction::Conjugate => arg.expect_num()?.conjugate(),
+                    .map_err(IntErr::into_string)?;
+                Self::String(crate::unary::to_unary(n)?.into())
+            }
+            BuiltInFunction::FromUnary => {
+                Self::Num(crate::unary::from_unary(&arg.expect_string()?)?.into())
+            }
+            BuiltInFunction::ToBrailleNumber => {
+                let n = arg.expect_num()?.try_as_u64(int)?;
+                Self::String(crate::braille::to_braille_number(n).into())
+            }
+            BuiltInFunction::FromBrailleNumber => {
+                let n = crate::braille::from_braille_number(&arg.expect_string()?)?;
+                Self::Num(n.into())
+            }
+            _ => unreachable!("{} is not an integer function", func),
+        })
+    }
+
+    // built-in functions that operate on integers with a bit width of at most 64
+    fn apply_bit_function<I: Interrupt>(
+        func: BuiltInFunction,
+        arg: Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let (n, width) = arg.expect_two_args()?;
+        let n = n.expect_num()?;
+        let width = width.expect_num()?.try_as_u32(int)?;
+        Ok(Self::Num(match func {
+            BuiltInFunction::CountLeadingZeros => {
+                let n = n.try_as_u64(int)?;
+                Number::from(u64::from(crate::bits::leading_zeros(n, width)?))
+            }
+            BuiltInFunction::SwapBytes => n.map_u64(|n| crate::bits::swap_bytes(n, width), int)?,
+            _ => unreachable!("{} is not a bit function", func),
+        }))
+    }
+
+    // implements `convert_currency(amount, currency, rate)`
+    fn convert_currency<I: Interrupt>(arg: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let [amount, to, rate] = arg.expect_nums()?;
+        Ok(Self::Num(amount.convert_currency(to, rate, int)?))
+    }
+
+    // implements `exponent(x, base)` and `mantissa(x, base)`
+    fn exponent_or_mantissa<I: Interrupt>(
+        func: BuiltInFunction,
+        arg: Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let (n, base) = arg.expect_two_args()?;
+        let (exponent, mantissa) = n
+            .expect_num()?
+            .exponent_and_mantissa(&base.expect_num()?, int)?;
+        Ok(Self::Num(if func == BuiltInFunction::Exponent {
+            exponent
+        } else {
+            mantissa
         }))
     }
 
+    // implements `convergents(x, n)`, returning the first `n` continued
+    // fraction convergents of `x` as a list
+    fn convergents<I: Interrupt>(arg: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let (x, count) = arg.expect_two_args()?;
+        let count = count.expect_num()?.try_as_u64(int)?;
+        let convergents = x.expect_num()?.continued_fraction_convergents(count, int)?;
+        Ok(Self::List(convergents.into_iter().map(Self::Num).collect()))
+    }
+
+    // implements `bernstein(n, k, t)`
+    fn bernstein<I: Interrupt>(arg: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let mut args = arg.expect_list()?.into_iter();
+        let (n, k, t) = match (args.next(), args.next(), args.next(), args.next()) {
+            (Some(n), Some(k), Some(t), None) => (
+                n.expect_num()?.try_as_u64(int)?,
+                k.expect_num()?.try_as_u64(int)?,
+                t.expect_num()?,
+            ),
+            _ => return Err("expected 3 arguments".to_string().into()),
+        };
+        Ok(Self::Num(Number::bernstein_polynomial(n, k, &t, int)?))
+    }
+
+    // implements `rotate_left(n, bits, width)` and `rotate_right(n, bits, width)`
+    fn rotate<I: Interrupt>(
+        func: BuiltInFunction,
+        arg: Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let mut args = arg.expect_list()?.into_iter();
+        let (n, bits, width) = match (args.next(), args.next(), args.next(), args.next()) {
+            (Some(n), Some(bits), Some(width), None) => (
+                n.expect_num()?,
+                bits.expect_num()?.try_as_u64(int)?,
+                width.expect_num()?.try_as_u32(int)?,
+            ),
+            _ => return Err("expected 3 arguments".to_string().into()),
+        };
+        let rotate_left = match func {
+            BuiltInFunction::RotateLeft => true,
+            BuiltInFunction::RotateRight => false,
+            _ => unreachable!("{} is not a rotation", func),
+        };
+        Ok(Self::Num(n.map_u64(
+            |n| crate::bits::rotate(n, bits, width, rotate_left),
+            int,
+        )?))
+    }
+
+    // built-in functions that take a string
+    fn apply_string_function<I: Interrupt>(
+        func: BuiltInFunction,
+        arg: Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let s = arg.expect_string()?;
+        Ok(match func {
+            BuiltInFunction::FromNato => Self::String(crate::nato::from_nato(&s).into()),
+            BuiltInFunction::HexDump => Self::String(hex_dump(&s).into()),
+            BuiltInFunction::HexToString => Self::String(hex_to_string(&s)?.into()),
+            BuiltInFunction::Crc32 => Self::Num(
+                Number::from(u64::from(crate::checksum::crc32(s.as_bytes()))).with_base(Base::HEX),
+            ),
+            BuiltInFunction::Crc16 => Self::Num(
+                Number::from(u64::from(crate::checksum::crc16(s.as_bytes()))).with_base(Base::HEX),
+            ),
+            BuiltInFunction::Djb2 => Self::Num(crate::hash::djb2(&s, int)?.into()),
+            BuiltInFunction::Fnv1a => Self::Num(crate::hash::fnv1a(&s, int)?.into()),
+            _ => unreachable!("{} is not a string function", func),
+        })
+    }
+
+    // built-in functions that convert to or from dates
+    fn apply_date_function<I: Interrupt>(
+        func: BuiltInFunction,
+        arg: Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        const SECONDS_PER_DAY: i64 = 86_400; // no leap seconds
+        Ok(match func {
+            BuiltInFunction::UnixTime => {
+                let seconds = arg.expect_date()?.days_since_epoch() * SECONDS_PER_DAY;
+                let magnitude = Number::from(seconds.unsigned_abs());
+                Self::Num(if seconds < 0 { -magnitude } else { magnitude })
+            }
+            BuiltInFunction::FromUnixTime => {
+                let seconds = arg.expect_num()?.try_as_i64(int)?;
+                Self::Date(
+                    crate::date::Date::from_days_since_epoch(seconds.div_euclid(SECONDS_PER_DAY))
+                        .map_err(|e| e.to_string())?,
+                )
+            }
+            BuiltInFunction::LunarPhase => Self::String(arg.expect_date()?.lunar_phase().into()),
+            BuiltInFunction::Season => {
+                // an optional second argument specifies the hemisphere
+                let (date, southern_hemisphere) = if let Self::List(_) = arg {
+                    let (date, hemisphere) = arg.expect_two_args()?;
+                    let southern = match hemisphere.expect_string()?.to_lowercase().as_str() {
+                        "north" | "northern" => false,
+                        "south" | "southern" => true,
+                        _ => {
+                            return Err("hemisphere must be \"north\" or \"south\""
+                                .to_string()
+                                .into())
+                        }
+                    };
+                    (date, southern)
+                } else {
+                    (arg, false)
+                };
+                Self::String(date.expect_date()?.season(southern_hemisphere).into())
+            }
+            BuiltInFunction::IsLeapYear | BuiltInFunction::DaysInYear => {
+                let days = crate::date::days_in_year(arg.expect_num()?.try_as_i64(int)?)?;
+                Self::Num(Number::from(if func == BuiltInFunction::DaysInYear {
+                    u64::from(days)
+                } else {
+                    u64::from(days == 366)
+                }))
+            }
+            BuiltInFunction::Easter | BuiltInFunction::EasterOrthodox => {
+                let year = arg.expect_num()?.try_as_i64(int)?;
+                Self::Date(crate::date::Date::easter(
+                    year,
+                    func == BuiltInFunction::EasterOrthodox,
+                )?)
+            }
+            _ => unreachable!("{} is not a date function", func),
+        })
+    }
+
+    // e.g. `time_zone_offset("EST")` => `-5 hours`
+    fn time_zone_offset<I: Interrupt>(
+        arg: Self,
+        scope: Option<Arc<Scope<'a>>>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let minutes = crate::timezones::offset_in_minutes(&arg.expect_string()?)?;
+        let hour = crate::ast::resolve_identifier(Ident::new("hour"), scope, context, int)?
+            .expect_num()?;
+        let magnitude = Number::from(u64::from(minutes.unsigned_abs()))
+            .div(Number::from(60), int)?
+            .mul(hour, int)?;
+        Ok(Self::Num(if minutes < 0 { -magnitude } else { magnitude }))
+    }
+
+    // number of days from today until the given date, negative for past dates
+    fn countdown<I: Interrupt>(
+        arg: Self,
+        scope: Option<Arc<Scope<'a>>>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let target = arg.expect_date()?;
+        let today = crate::date::Date::today(context).map_err(|e| e.to_string())?;
+        let days = target.days_since_epoch() - today.days_since_epoch();
+        let day =
+            crate::ast::resolve_identifier(Ident::new("day"), scope, context, int)?.expect_num()?;
+        let magnitude = Number::from(days.unsigned_abs()).mul(day, int)?;
+        Ok(Self::Num(if days < 0 { -magnitude } else { magnitude }))
+    }
+
+    // e.g. `age("1990-01-15" as date)` => `34 years, 2 months, 11 days`
+    fn age<I: Interrupt>(
+        arg: Self,
+        context: &mut crate::Context,
+    ) -> Result<Self, IntErr<String, I>> {
+        let birth_date = arg.expect_date()?;
+        let today = crate::date::Date::today(context).map_err(|e| e.to_string())?;
+        let (years, months, days) = birth_date.age_at(today)?;
+        let parts = [(years, "year"), (months, "month"), (days, "day")]
+            .iter()
+            .map(|&(n, name)| format!("{} {}{}", n, name, if n == 1 { "" } else { "s" }))
+            .collect::<Vec<_>>();
+        Ok(Self::String(parts.join(", ").into()))
+    }
+
+    // e.g. `format_number(1234567.891, "#,##0.00")` => `1,234,567.89`
+    fn format_number<I: Interrupt>(arg: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let (x, template) = arg.expect_two_args()?;
+        let template = crate::format::template::Template::parse(&template.expect_string()?)?;
+        let fixed = x
+            .expect_num()?
+            .format_fixed(template.max_decimal_places(), int)?;
+        Ok(Self::String(template.apply(&fixed).into()))
+    }
+
+    // e.g. `nth(1, (3, 4, 5))` => `3`
+    fn nth<I: Interrupt>(arg: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let (n, list) = arg.expect_two_args()?;
+        let n = n
+            .expect_num()?
+            .try_as_usize(int)
+            .map_err(IntErr::into_string)?;
+        let mut items = list.expect_list()?;
+        if n == 0 || n > items.len() {
+            return Err(format!(
+                "index {} is out of range for a list of length {}",
+                n,
+                items.len()
+            )
+            .into());
+        }
+        Ok(items.swap_remove(n - 1))
+    }
+
+    // implements `zip(f, a, b)`
+    fn zip<I: Interrupt>(
+        arg: Self,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let mut args = arg.expect_list()?.into_iter();
+        let (func, a, b) = match (args.next(), args.next(), args.next(), args.next()) {
+            (Some(func), Some(a), Some(b), None) => (func, a.expect_list()?, b.expect_list()?),
+            _ => return Err("expected 3 arguments".to_string().into()),
+        };
+        if a.len() != b.len() {
+            return Err(format!(
+                "cannot zip lists of different lengths ({} and {})",
+                a.len(),
+                b.len()
+            )
+            .into());
+        }
+        let mut result = vec![];
+        for (x, y) in a.into_iter().zip(b) {
+            crate::interrupt::test_int(int).map_err(IntErr::into_string)?;
+            result.push(func.clone().apply_to_values(vec![x, y], context, int)?);
+        }
+        Ok(Self::List(result))
+    }
+
+    // implements `map(f, list)`
+    fn map<I: Interrupt>(
+        arg: Self,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let (func, list) = arg.expect_two_args()?;
+        let mut result = vec![];
+        for x in list.expect_list()? {
+            crate::interrupt::test_int(int).map_err(IntErr::into_string)?;
+            result.push(func.clone().apply_to_values(vec![x], context, int)?);
+        }
+        Ok(Self::List(result))
+    }
+
+    // implements `filter(pred, list)`, keeping elements where `pred` is non-zero
+    fn filter<I: Interrupt>(
+        arg: Self,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let (pred, list) = arg.expect_two_args()?;
+        let mut result = vec![];
+        for x in list.expect_list()? {
+            crate::interrupt::test_int(int).map_err(IntErr::into_string)?;
+            let keep = pred
+                .clone()
+                .apply_to_values(vec![x.clone()], context, int)?
+                .expect_num()?;
+            if !keep.is_zero() {
+                result.push(x);
+            }
+        }
+        Ok(Self::List(result))
+    }
+
+    // implements `fold(f, initial, list)`, applying `f` from left to right
+    fn fold<I: Interrupt>(
+        arg: Self,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let mut args = arg.expect_list()?.into_iter();
+        let (func, mut acc, list) = match (args.next(), args.next(), args.next(), args.next()) {
+            (Some(func), Some(initial), Some(list), None) => (func, initial, list.expect_list()?),
+            _ => return Err("expected 3 arguments".to_string().into()),
+        };
+        for x in list {
+            crate::interrupt::test_int(int).map_err(IntErr::into_string)?;
+            acc = func.clone().apply_to_values(vec![acc, x], context, int)?;
+        }
+        Ok(acc)
+    }
+
+    // implements `apply_at(f, n, x)`, i.e. `f(f(...f(x)))` with `n` applications of `f`
+    fn apply_at<I: Interrupt>(
+        arg: Self,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let mut args = arg.expect_list()?.into_iter();
+        let (func, n, mut x) = match (args.next(), args.next(), args.next(), args.next()) {
+            (Some(func), Some(n), Some(x), None) => (func, n.expect_num()?.try_as_u64(int)?, x),
+            _ => return Err("expected 3 arguments".to_string().into()),
+        };
+        for _ in 0..n {
+            crate::interrupt::test_int(int).map_err(IntErr::into_string)?;
+            x = func.clone().apply_to_values(vec![x], context, int)?;
+        }
+        Ok(x)
+    }
+
+    // implements `range(start, end)` and `range(start, end, step)`,
+    // where `end` is only included if `include_end` is set
+    fn range<I: Interrupt>(
+        arg: Self,
+        include_end: bool,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let mut args = arg.expect_list()?.into_iter();
+        let (start, end, step) = match (args.next(), args.next(), args.next(), args.next()) {
+            (Some(start), Some(end), step, None) => (
+                start.expect_num()?,
+                end.expect_num()?,
+                match step {
+                    Some(step) => step.expect_num()?,
+                    None => Number::from(1),
+                },
+            ),
+            _ => return Err("expected 2 or 3 arguments".to_string().into()),
+        };
+        if step.is_zero() {
+            return Err("step of a range cannot be zero".to_string().into());
+        }
+        let ascending = !step.is_negative();
+        let mut result = vec![];
+        let mut x = start;
+        loop {
+            crate::interrupt::test_int(int).map_err(IntErr::into_string)?;
+            let ordering = x.clone().compare(end.clone(), int)?;
+            let past_end = if ascending {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+            if ordering == past_end || ordering == Ordering::Equal && !include_end {
+                break;
+            }
+            result.push(Self::Num(x.clone()));
+            x = x.add(step.clone(), int)?;
+        }
+        Ok(Self::List(result))
+    }
+
+    // implements `tabulate(f, start, end)` and `tabulate(f, start, end, step)`,
+    // returning a table with the inputs (including `end`) and outputs of `f`
+    // in two columns
+    fn tabulate<I: Interrupt>(
+        arg: Self,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let mut args = arg.expect_list()?;
+        if args.len() != 3 && args.len() != 4 {
+            return Err("expected 3 or 4 arguments".to_string().into());
+        }
+        let func = args.remove(0);
+        // in e.g. `tabulate(sin, 0, 360 degrees, 30 degrees)` the start has
+        // no unit, so show all inputs in the first unit that was given
+        let display_unit = args.iter().find_map(|arg| match arg {
+            Self::Num(n) if !n.is_unitless() => Some(n.clone()),
+            _ => None,
+        });
+        let mut rows = vec![];
+        for x in Self::range(Self::List(args), true, int)?.expect_list()? {
+            let mut x = x.expect_num()?;
+            if let Some(unit) = &display_unit {
+                x = x.convert_to_unit_of(unit, int)?;
+            }
+            let x = Self::Num(x);
+            let y = func
+                .clone()
+                .apply_to_values(vec![x.clone()], context, int)?;
+            rows.push((
+                x.format_to_plain_string(0, int)?,
+                y.format_to_plain_string(0, int)?,
+            ));
+        }
+        let width = rows
+            .iter()
+            .map(|(x, _)| x.chars().count())
+            .max()
+            .unwrap_or(0);
+        let table = rows
+            .into_iter()
+            .map(|(x, y)| format!("{}{} | {}", x, " ".repeat(width - x.chars().count()), y))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok(Self::String(table.into()))
+    }
+
+    // implements `histogram(list, bins)`, counting how many elements fall
+    // into each of `bins` equal-width buckets between the minimum and maximum
+    fn histogram<I: Interrupt>(arg: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let (list, bins) = arg.expect_two_args()?;
+        let bins = bins
+            .expect_num()?
+            .try_as_usize(int)
+            .map_err(IntErr::into_string)?;
+        if bins == 0 {
+            return Err("number of bins must be positive".to_string().into());
+        }
+        let mut values = vec![];
+        for x in list.expect_list()? {
+            values.push(x.expect_num()?);
+        }
+        let (min, max) = Self::min_and_max(&values, int)?;
+        let mut counts = vec![0_u64; bins];
+        if min.clone().compare(max.clone(), int)? == Ordering::Equal {
+            // all values are equal, so the buckets span a width of 1 centred
+            // on that value, which puts every value into the middle bucket
+            counts[bins / 2] = values.len() as u64;
+        } else {
+            let width = max
+                .sub(min.clone(), int)?
+                .div(Number::from(bins as u64), int)?;
+            // the upper boundaries of all buckets except for the last one
+            let mut boundaries = vec![];
+            let mut boundary = min;
+            for _ in 1..bins {
+                boundary = boundary.add(width.clone(), int)?;
+                boundaries.push(boundary.clone());
+            }
+            for x in values {
+                let mut bucket = bins - 1;
+                for (i, boundary) in boundaries.iter().enumerate() {
+                    crate::interrupt::test_int(int).map_err(IntErr::into_string)?;
+                    if x.clone().compare(boundary.clone(), int)? == Ordering::Less {
+                        bucket = i;
+                        break;
+                    }
+                }
+                counts[bucket] += 1;
+            }
+        }
+        Ok(Self::List(
+            counts
+                .into_iter()
+                .map(|count| Self::Num(Number::from(count)))
+                .collect(),
+        ))
+    }
+
+    fn min_and_max<I: Interrupt>(
+        values: &[Number<'a>],
+        int: &I,
+    ) -> Result<(Number<'a>, Number<'a>), IntErr<String, I>> {
+        let mut values = values.iter();
+        let first = values
+            .next()
+            .ok_or_else(|| "expected a non-empty list".to_string())?;
+        let (mut min, mut max) = (first.clone(), first.clone());
+        for x in values {
+            crate::interrupt::test_int(int).map_err(IntErr::into_string)?;
+            if x.clone().compare(min.clone(), int)? == Ordering::Less {
+                min = x.clone();
+            }
+            if x.clone().compare(max.clone(), int)? == Ordering::Greater {
+                max = x.clone();
+            }
+        }
+        Ok((min, max))
+    }
+
+    // built-in functions that summarise a non-empty list of numbers
+    fn apply_statistics_function<I: Interrupt>(
+        func: BuiltInFunction,
+        arg: Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let mut values = vec![];
+        for x in arg.expect_list()? {
+            values.push(x.expect_num()?);
+        }
+        if values.is_empty() {
+            return Err("expected a non-empty list".to_string().into());
+        }
+        let half = || Number::from(1).div(Number::from(2), int);
+        Ok(Self::Num(match func {
+            BuiltInFunction::Mean => Self::mean(values, int)?,
+            BuiltInFunction::Median => Self::median(values, int)?,
+            BuiltInFunction::Variance => Self::variance(values, false, int)?,
+            BuiltInFunction::VarianceSample => Self::variance(values, true, int)?,
+            BuiltInFunction::Stddev => Self::variance(values, false, int)?.pow(half()?, int)?,
+            BuiltInFunction::StddevSample => {
+                Self::variance(values, true, int)?.pow(half()?, int)?
+            }
+            _ => unreachable!(),
+        }))
+    }
+
+    fn mean<I: Interrupt>(
+        values: Vec<Number<'a>>,
+        int: &I,
+    ) -> Result<Number<'a>, IntErr<String, I>> {
+        let count = Number::from(values.len() as u64);
+        Self::total(values, int)?.div(count, int)
+    }
+
+    fn median<I: Interrupt>(
+        values: Vec<Number<'a>>,
+        int: &I,
+    ) -> Result<Number<'a>, IntErr<String, I>> {
+        // insertion sort, since comparing two numbers can fail
+        let mut sorted: Vec<Number<'a>> = vec![];
+        for x in values {
+            let mut idx = sorted.len();
+            while idx > 0 {
+                crate::interrupt::test_int(int).map_err(IntErr::into_string)?;
+                if x.clone().compare(sorted[idx - 1].clone(), int)? != Ordering::Less {
+                    break;
+                }
+                idx -= 1;
+            }
+            sorted.insert(idx, x);
+        }
+        let middle = sorted.len() / 2;
+        if sorted.len() % 2 == 1 {
+            Ok(sorted.swap_remove(middle))
+        } else {
+            let upper = sorted.swap_remove(middle);
+            let lower = sorted.swap_remove(middle - 1);
+            Self::mean(vec![lower, upper], int)
+        }
+    }
+
+    fn variance<I: Interrupt>(
+        values: Vec<Number<'a>>,
+        sample: bool,
+        int: &I,
+    ) -> Result<Number<'a>, IntErr<String, I>> {
+        let count = values.len() as u64;
+        if sample && count < 2 {
+            return Err("sample variance requires at least 2 values"
+                .to_string()
+                .into());
+        }
+        let mean = Self::mean(values.clone(), int)?;
+        let mut squared_deviations = vec![];
+        for x in values {
+            let deviation = x.sub(mean.clone(), int)?;
+            squared_deviations.push(deviation.clone().mul(deviation, int)?);
+        }
+        Self::total(squared_deviations, int)?
+            .div(Number::from(if sample { count - 1 } else { count }), int)
+    }
+
+    fn sum<I: Interrupt>(arg: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let mut values = vec![];
+        for x in arg.expect_list()? {
+            values.push(x.expect_num()?);
+        }
+        Ok(Self::Num(Self::total(values, int)?))
+    }
+
+    // the sum of all values, in the unit of the first value
+    fn total<I: Interrupt>(
+        values: Vec<Number<'a>>,
+        int: &I,
+    ) -> Result<Number<'a>, IntErr<String, I>> {
+        let mut total: Option<Number<'a>> = None;
+        for x in values {
+            crate::interrupt::test_int(int).map_err(IntErr::into_string)?;
+            total = Some(match total {
+                Some(total) => total.add(x, int)?,
+                None => x,
+            });
+        }
+        Ok(total.unwrap_or_else(|| Number::from(0)))
+    }
+
+    fn expect_nums<I: Interrupt, const N: usize>(
+        self,
+    ) -> Result<[Number<'a>; N], IntErr<String, I>> {
+        let args = self.expect_list()?;
+        if args.len() != N {
+            return Err(format!("expected {} arguments", N).into());
+        }
+        let mut nums = vec![];
+        for arg in args {
+            nums.push(arg.expect_num()?);
+        }
+        match std::convert::TryInto::try_into(nums) {
+            Ok(nums) => Ok(nums),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    // like `expect_nums`, but exactly one of the arguments must be `?`,
+    // returning its index (the placeholder itself is replaced by 1)
+    fn expect_nums_with_unknown<I: Interrupt, const N: usize>(
+        self,
+    ) -> Result<([Number<'a>; N], usize), IntErr<String, I>> {
+        let args = self.expect_list()?;
+        if args.len() != N {
+            return Err(format!("expected {} arguments", N).into());
+        }
+        let mut unknown = None;
+        let mut nums = vec![];
+        for (i, arg) in args.into_iter().enumerate() {
+            if let Self::Unknown = arg {
+                if unknown.is_some() {
+                    return Err("exactly one argument must be ?".to_string().into());
+                }
+                unknown = Some(i);
+                nums.push(Number::from(1));
+            } else {
+                nums.push(arg.expect_num()?);
+            }
+        }
+        let unknown = unknown.ok_or_else(|| "exactly one argument must be ?".to_string())?;
+        match std::convert::TryInto::try_into(nums) {
+            Ok(nums) => Ok((nums, unknown)),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    // implements `parallel(a, b, ...)`, i.e. `1 / (1/a + 1/b + ...)`
+    fn parallel<I: Interrupt>(arg: Self, int: &I) -> Result<Self, IntErr<String, I>> {
+        let args = arg.expect_list()?;
+        if args.len() < 2 {
+            return Err("expected at least 2 arguments".to_string().into());
+        }
+        let mut reciprocals = vec![];
+        for x in args {
+            reciprocals.push(Number::from(1).div(x.expect_num()?, int)?);
+        }
+        Ok(Self::Num(
+            Number::from(1).div(Self::total(reciprocals, int)?, int)?,
+        ))
+    }
+
+    // implements `ohms_law(v, i, r)`, solving `v = i * r` for the argument
+    // given as `?`
+    fn ohms_law<I: Interrupt>(
+        arg: Self,
+        scope: Option<Arc<Scope<'a>>>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let ([v, i, r], unknown) = arg.expect_nums_with_unknown()?;
+        let (result, unit) = match unknown {
+            0 => (i.mul(r, int)?, "V"),
+            1 => (v.div(r, int)?, "A"),
+            _ => (v.div(i, int)?, "ohm"),
+        };
+        Ok(Self::Num(Self::convert_to_named_unit(
+            result, unit, scope, context, int,
+        )?))
+    }
+
+    // implements `ideal_gas(p, v, n, t)`, solving `p v = n R t` for the
+    // argument given as `?`
+    fn ideal_gas<I: Interrupt>(
+        arg: Self,
+        scope: Option<Arc<Scope<'a>>>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let ([p, v, n, mut t], unknown) = arg.expect_nums_with_unknown()?;
+        let gas_constant = crate::ast::resolve_identifier(
+            Ident::new("gas_constant"),
+            scope.clone(),
+            context,
+            int,
+        )?
+        .expect_num()?;
+        if unknown != 3 {
+            // temperatures like `25 °C` need to be converted to an absolute scale
+            t = Self::convert_to_named_unit(t, "K", scope.clone(), context, int)?;
+        }
+        let (result, unit) = match unknown {
+            0 => (n.mul(gas_constant, int)?.mul(t, int)?.div(v, int)?, "Pa"),
+            1 => (n.mul(gas_constant, int)?.mul(t, int)?.div(p, int)?, "L"),
+            2 => (p.mul(v, int)?.div(gas_constant.mul(t, int)?, int)?, "mol"),
+            _ => (p.mul(v, int)?.div(n.mul(gas_constant, int)?, int)?, "K"),
+        };
+        Ok(Self::Num(Self::convert_to_named_unit(
+            result, unit, scope, context, int,
+        )?))
+    }
+
+    // implements `rc_time_constant(r, c)` and `lc_resonance(l, c)`
+    fn apply_circuit_function<I: Interrupt>(
+        func: BuiltInFunction,
+        arg: Self,
+        scope: Option<Arc<Scope<'a>>>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let [a, c] = arg.expect_nums()?;
+        let (result, unit) = match func {
+            BuiltInFunction::RcTimeConstant => (a.mul(c, int)?, "s"),
+            BuiltInFunction::LcResonance => {
+                let half = Number::from(1).div(Number::from(2), int)?;
+                let two_pi = Number::pi().mul(Number::from(2), int)?;
+                let denominator = two_pi.mul(a.mul(c, int)?.pow(half, int)?, int)?;
+                (Number::from(1).div(denominator, int)?, "Hz")
+            }
+            _ => unreachable!("{} is not a circuit function", func),
+        };
+        Ok(Self::Num(Self::convert_to_named_unit(
+            result, unit, scope, context, int,
+        )?))
+    }
+
+    // implements `snell(n1, theta1, n2)`, returning the angle of refraction
+    // in the same unit as `theta1`
+    fn snell<I: Interrupt>(
+        arg: Self,
+        scope: Option<Arc<Scope<'a>>>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let [n1, theta1, n2] = arg.expect_nums()?;
+        let sin_theta2 = n1
+            .div(n2, int)?
+            .mul(theta1.clone().sin(scope.clone(), context, int)?, int)?;
+        if sin_theta2.clone().abs(int)?.compare(Number::from(1), int)? == Ordering::Greater {
+            return Err("total internal reflection: there is no refracted ray"
+                .to_string()
+                .into());
+        }
+        let radians = crate::ast::resolve_identifier(Ident::new("radians"), scope, context, int)?
+            .expect_num()?;
+        let theta2 = sin_theta2.asin(int)?.mul(radians, int)?;
+        Ok(Self::Num(theta2.convert_to_unit_of(&theta1, int)?))
+    }
+
+    // converts a number to a unit like `ohm`, failing if the units are incompatible
+    fn convert_to_named_unit<I: Interrupt>(
+        n: Number<'a>,
+        unit: &'static str,
+        scope: Option<Arc<Scope<'a>>>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Number<'a>, IntErr<String, I>> {
+        let unit =
+            crate::ast::resolve_identifier(Ident::new(unit), scope, context, int)?.expect_num()?;
+        n.convert_to(unit, int)
+    }
+
+    // implements `taxi_distance(x1, y1, x2, y2)` and
+    // `chebyshev_distance(x1, y1, x2, y2)`, returning a distance in the
+    // units of `x1`
+    fn grid_distance<I: Interrupt>(
+        func: BuiltInFunction,
+        arg: Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let [x1, y1, x2, y2] = arg.expect_nums()?;
+        let dx = x1.sub(x2, int)?.abs(int)?;
+        let dy = y1.sub(y2, int)?.abs(int)?.convert_to_unit_of(&dx, int)?;
+        Ok(Self::Num(match func {
+            BuiltInFunction::TaxiDistance => dx.add(dy, int)?,
+            BuiltInFunction::ChebyshevDistance => {
+                if dx.clone().compare(dy.clone(), int)? == Ordering::Less {
+                    dy
+                } else {
+                    dx
+                }
+            }
+            _ => unreachable!("{} is not a distance function", func),
+        }))
+    }
+
+    // implements `haversine(lat1, lon1, lat2, lon2)`, returning the
+    // great-circle distance on the surface of the earth in metres
+    fn haversine<I: Interrupt>(
+        arg: Self,
+        scope: Option<Arc<Scope<'a>>>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let [lat1, lon1, lat2, lon2] = Self::expect_coordinates(arg, scope.as_ref(), context, int)?;
+        let hav_lat = Self::haversine_of(
+            lat2.clone().sub(lat1.clone(), int)?,
+            scope.as_ref(),
+            context,
+            int,
+        )?;
+        let hav_lon = Self::haversine_of(lon2.sub(lon1, int)?, scope.as_ref(), context, int)?;
+        let cos_lat1 = lat1.cos(scope.clone(), context, int)?;
+        let cos_lat2 = lat2.cos(scope.clone(), context, int)?;
+        let hav = hav_lat.add(cos_lat1.mul(cos_lat2, int)?.mul(hav_lon, int)?, int)?;
+        let half = Number::from(1).div(Number::from(2), int)?;
+        let central_angle = hav.pow(half, int)?.asin(int)?.mul(Number::from(2), int)?;
+        let radius = crate::eval::evaluate_to_value("radius of earth to m", scope, context, int)?
+            .expect_num()?;
+        Ok(Self::Num(radius.mul(central_angle, int)?))
+    }
+
+    // implements `compass_bearing(lat1, lon1, lat2, lon2)`, returning the
+    // initial bearing in degrees clockwise from north
+    fn compass_bearing<I: Interrupt>(
+        arg: Self,
+        scope: Option<Arc<Scope<'a>>>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let [lat1, lon1, lat2, lon2] = Self::expect_coordinates(arg, scope.as_ref(), context, int)?;
+        let delta_lon = lon2.sub(lon1, int)?;
+        let sin_lat1 = lat1.clone().sin(scope.clone(), context, int)?;
+        let cos_lat1 = lat1.cos(scope.clone(), context, int)?;
+        let sin_lat2 = lat2.clone().sin(scope.clone(), context, int)?;
+        let cos_lat2 = lat2.cos(scope.clone(), context, int)?;
+        let y = delta_lon
+            .clone()
+            .sin(scope.clone(), context, int)?
+            .mul(cos_lat2.clone(), int)?;
+        let x = cos_lat1.mul(sin_lat2, int)?.sub(
+            sin_lat1
+                .mul(cos_lat2, int)?
+                .mul(delta_lon.cos(scope.clone(), context, int)?, int)?,
+            int,
+        )?;
+        let mut bearing = y.atan2(x, int)?;
+        if bearing.is_negative() {
+            bearing = bearing.add(Number::pi().mul(Number::from(2), int)?, int)?;
+        }
+        let radians =
+            crate::ast::resolve_identifier(Ident::new("radians"), scope.clone(), context, int)?
+                .expect_num()?;
+        let degrees = crate::ast::resolve_identifier(Ident::new("degrees"), scope, context, int)?
+            .expect_num()?;
+        Ok(Self::Num(
+            bearing.mul(radians, int)?.convert_to(degrees, int)?,
+        ))
+    }
+
+    // the latitudes and longitudes of two points, where numbers without a
+    // unit are in degrees (rather than radians) as is usual for coordinates
+    fn expect_coordinates<I: Interrupt>(
+        arg: Self,
+        scope: Option<&Arc<Scope<'a>>>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<[Number<'a>; 4], IntErr<String, I>> {
+        let mut coordinates: [Number<'a>; 4] = arg.expect_nums()?;
+        let degree =
+            crate::ast::resolve_identifier(Ident::new("degree"), scope.cloned(), context, int)?
+                .expect_num()?;
+        for coordinate in &mut coordinates {
+            if coordinate.is_unitless() {
+                *coordinate = coordinate.clone().mul(degree.clone(), int)?;
+            }
+        }
+        Ok(coordinates)
+    }
+
+    // computes `sin(angle / 2)^2`
+    fn haversine_of<I: Interrupt>(
+        angle: Number<'a>,
+        scope: Option<&Arc<Scope<'a>>>,
+        context: &mut crate::Context,
+        int: &I,
+    ) -> Result<Number<'a>, IntErr<String, I>> {
+        let sin = angle
+            .div(Number::from(2), int)?
+            .sin(scope.cloned(), context, int)?;
+        sin.clone().mul(sin, int)
+    }
+
+    // implements `to_fixed(x, digits)` and `to_precision(x, digits)`
+    fn format_with_digits<I: Interrupt>(
+        func: BuiltInFunction,
+        arg: Self,
+        int: &I,
+    ) -> Result<Self, IntErr<String, I>> {
+        let (x, digits) = arg.expect_two_args()?;
+        let x = x.expect_num()?;
+        let digits = digits
+            .expect_num()?
+            .try_as_usize(int)
+            .map_err(IntErr::into_string)?;
+        let formatted = if func == BuiltInFunction::ToFixed {
+            x.format_fixed(digits, int)?
+        } else if digits == 0 {
+            return Err("cannot format a number with zero significant figures"
+                .to_string()
+                .into());
+        } else {
+            x.format_precision(digits, int)?
+        };
+        Ok(Self::String(formatted.into()))
+    }
+
     pub(crate) fn format_to_plain_string<I: Interrupt>(
         &self,
         indent: usize,
@@ -327,6 +2011,12 @@ impl<'a> Value<'a> {
                     kind: SpanKind::Keyword,
                 });
             }
+            Self::Unknown => {
+                spans.push(Span {
+                    string: "?".to_string(),
+                    kind: SpanKind::Keyword,
+                });
+            }
             Self::Base(b) => {
                 spans.push(Span {
                     string: "base ".to_string(),
@@ -376,6 +2066,18 @@ impl<'a> Value<'a> {
                     kind: SpanKind::Date,
                 });
             }
+            Self::List(items) => {
+                spans.push(Span::from_string("(".to_string()));
+                for (i, item) in items.iter().enumerate() {
+                    if i != 0 {
+                        spans.push(Span::from_string(", ".to_string()));
+                    }
+                    item.format(indent, spans, int)?;
+                }
+                spans.push(Span::from_string(")".to_string()));
+            }
+            Self::Measurement(m) => m.spans(spans, int)?,
+            Self::Memo(func, _) => func.format(indent, spans, int)?,
         }
         Ok(())
     }
@@ -390,6 +2092,7 @@ impl<'a> Value<'a> {
                 }
                 Err("could not find key in object")
             }
+            Self::Date(d) => d.get_object_member(key.as_str()),
             _ => Err("expected an object"),
         }
     }
@@ -421,6 +2124,7 @@ impl<'a> fmt::Debug for Value<'a> {
             Self::Format(fmt) => write!(f, "format: {:?}", fmt),
             Self::Dp => write!(f, "dp"),
             Self::Sf => write!(f, "sf"),
+            Self::Unknown => write!(f, "?"),
             Self::Base(b) => write!(f, "base: {:?}", b),
             Self::Fn(name, expr, scope) => {
                 write!(f, "fn: {} => {:?} (scope: {:?})", name, expr, scope)
@@ -438,6 +2142,58 @@ impl<'a> fmt::Debug for Value<'a> {
             }
             Self::String(s) => write!(f, r#""{}""#, s.as_ref()),
             Self::Date(d) => write!(f, "{:?}", d),
+            Self::List(items) => write!(f, "list: {:?}", items),
+            Self::Measurement(m) => write!(f, "measurement: {:?}", m),
+            Self::Memo(func, _) => write!(f, "memo: {:?}", func),
+        }
+    }
+}
+
+// adds the arguments of a function call like `f(a, b)` to a stack of
+// arguments that still need to be applied, so that `a` is on top
+fn push_args<'a>(
+    arg: Expr<'a>,
+    scope: Option<Arc<Scope<'a>>>,
+    pending_args: &mut Vec<(Expr<'a>, Option<Arc<Scope<'a>>>)>,
+) {
+    match arg {
+        Expr::Parens(inner) => match *inner {
+            Expr::List(args) => {
+                for arg in args.into_iter().rev() {
+                    pending_args.push((arg, scope.clone()));
+                }
+            }
+            inner => pending_args.push((Expr::Parens(Box::new(inner)), scope)),
+        },
+        arg => pending_args.push((arg, scope)),
+    }
+}
+
+// e.g. `ABC` => `41 42 43`
+fn hex_dump(s: &str) -> String {
+    s.bytes()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// e.g. `41 42 43` => `ABC`
+fn hex_to_string(s: &str) -> Result<String, String> {
+    let digits = s
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .collect::<Vec<_>>();
+    if digits.len() % 2 != 0 {
+        return Err("expected an even number of hex digits".to_string());
+    }
+    let mut bytes = vec![];
+    for pair in digits.chunks(2) {
+        let pair = pair.iter().collect::<String>();
+        // `from_str_radix` would also accept a leading `+`
+        if !pair.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return Err(format!("invalid hex byte '{}'", pair));
         }
+        bytes.push(u8::from_str_radix(&pair, 16).map_err(|e| e.to_string())?);
     }
+    String::from_utf8(bytes).map_err(|_| "bytes are not valid UTF-8".to_string())
 }
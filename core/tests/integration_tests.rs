@@ -1505,17 +1505,17 @@ fn digit_separators_14() {
 
 #[test]
 fn digit_separators_15() {
-    test_eval("1,1", "11");
+    test_eval_simple("1,1", "(1, 1)");
 }
 
 #[test]
 fn digit_separators_16() {
-    test_eval("11,1", "111");
+    test_eval_simple("11,1", "(11, 1)");
 }
 
 #[test]
 fn digit_separators_17() {
-    test_eval("1,1,1", "111");
+    test_eval_simple("1,1,1", "(1, 1, 1)");
 }
 
 #[test]
@@ -1525,17 +1525,17 @@ fn digit_separators_18() {
 
 #[test]
 fn digit_separators_19() {
-    test_eval("1,2,3,4,5,6", "123456");
+    test_eval_simple("1,2,3,4,5,6", "(1, 2, 3, 4, 5, 6)");
 }
 
 #[test]
 fn digit_separators_20() {
-    test_eval("1.1,1", "1.11");
+    test_eval_simple("1.1,1", "(1.1, 1)");
 }
 
 #[test]
 fn digit_separators_21() {
-    test_eval("1,1.1,1", "11.11");
+    test_eval_simple("1,1.1,1", "(1, 1.1, 1)");
 }
 
 #[test]
@@ -1560,7 +1560,7 @@ fn digit_separators_25() {
 
 #[test]
 fn digit_separators_26() {
-    expect_error("1,.1", None);
+    test_eval_simple("1,.1", "(1, 0.1)");
 }
 
 #[test]
@@ -1573,6 +1573,41 @@ fn digit_separators_28() {
     expect_error("1.1,", None);
 }
 
+#[test]
+fn digit_separators_29() {
+    test_eval("1,234,567", "1234567");
+}
+
+#[test]
+fn digit_separators_30() {
+    test_eval("1,234,567.89 * 2", "2469135.78");
+}
+
+#[test]
+fn digit_separators_31() {
+    test_eval("1,000 + 2,500", "3500");
+}
+
+#[test]
+fn digit_separators_32() {
+    test_eval_simple("to_fixed(1,234.5, 2)", "1234.50");
+}
+
+#[test]
+fn digit_separators_33() {
+    test_eval_simple("1,23", "(1, 23)");
+}
+
+#[test]
+fn digit_separators_34() {
+    test_eval_simple("1,2345", "(1, 2345)");
+}
+
+#[test]
+fn digit_separators_35() {
+    test_eval_simple("1,234,5678", "(1234, 5678)");
+}
+
 #[test]
 fn different_base_1() {
     test_eval("0x10", "0x10");
@@ -4467,6 +4502,61 @@ fn mass_of_earth() {
     test_eval("mass of earth", "5972370000000000000000000 kg");
 }
 
+#[test]
+fn radius_of_earth() {
+    test_eval("radius of earth", "6371 km");
+    test_eval("diameter of earth", "12742 km");
+    test_eval("circumference of earth", "40075.017 km");
+}
+
+#[test]
+fn surface_area_of_earth() {
+    test_eval("surface_area of earth", "510072000 km^2");
+}
+
+#[test]
+fn atmospheric_pressure_of_earth() {
+    test_eval("atmospheric_pressure of earth", "101325 Pa");
+    test_eval("atmospheric_pressure of earth to kPa", "101.325 kPa");
+}
+
+#[test]
+fn rotation_period_of_earth() {
+    test_eval("rotation_period of earth", "86164.0905 s");
+    test_eval("solar_day of earth to hours", "24 hours");
+}
+
+#[test]
+fn orbital_period_of_earth() {
+    test_eval("orbital_period of earth", "365.256363004 days");
+}
+
+#[test]
+fn moon_properties() {
+    test_eval("mass of moon", "73460000000000000000000 kg");
+    test_eval("radius of moon", "1737.4 km");
+    test_eval("orbital_period of moon", "27.321661 days");
+}
+
+#[test]
+fn sun_properties() {
+    test_eval("radius of sun", "695700 km");
+    test_eval("gravity of sun", "274 m / s^2");
+    test_eval("escape_velocity of sun", "617.6 km / s");
+}
+
+#[test]
+fn planet_properties() {
+    test_eval("orbital_period of mars", "686.98 days");
+    test_eval("gravity of venus", "8.87 m / s^2");
+    test_eval("radius of jupiter", "69911 km");
+}
+
+#[test]
+fn compare_planet_gravity() {
+    test_eval("gravity of mars / gravity of earth", "approx. 0.3803541474");
+}
+
 #[test]
 fn maths_with_earth_properties() {
     test_eval(
@@ -5075,6 +5165,19 @@ fn today() {
     );
 }
 
+#[test]
+fn reset_context() {
+    let mut context = Context::new();
+    context.set_current_time_v1(1617517099000, 0);
+    assert!(evaluate("today", &mut context).is_ok());
+    context.reset();
+    assert!(evaluate("today", &mut context).is_err());
+    assert_eq!(
+        evaluate("1 + 1", &mut context).unwrap().get_main_result(),
+        "2"
+    );
+}
+
 #[test]
 #[ignore]
 fn today_with_tz() {
@@ -5107,7 +5210,6 @@ fn single_line_comment() {
 }
 
 #[test]
-#[ignore]
 fn single_line_comment_and_linebreak() {
     test_eval("30.48cm to \' # converting cm to feet\n", "1'");
 }
@@ -5123,6 +5225,23 @@ fn single_line_comment_and_linebreak_3() {
     test_eval("30.48cm to \' # converting cm\n ' # to feet", "1'");
 }
 
+#[test]
+fn empty_comment() {
+    test_eval("1 + 1 #", "2");
+    test_eval("1 + #\n1", "2");
+}
+
+#[test]
+fn multi_line_comments() {
+    test_eval("1 # first line\n# second line\n+ 2 # third line", "3");
+    test_eval("# leading comment\n5 kg", "5 kg");
+}
+
+#[test]
+fn comment_does_not_affect_raw_strings() {
+    test_eval_simple("#\"# not a comment\"#", "# not a comment");
+}
+
 #[test]
 fn percent_plus_per_mille() {
     test_eval("4% + 3\u{2030}", "4.3%");
@@ -5186,3 +5305,1868 @@ fn not_simplify_explicit_to() {
 fn proper_unit_priority() {
     test_eval_simple("50 meters / 5 seconds", "10 meters / second");
 }
+
+#[test]
+fn roman_numerals() {
+    test_eval_simple("1 as roman", "I");
+    test_eval_simple("1994 as roman", "MCMXCIV");
+    test_eval_simple("3999 as roman", "MMMCMXCIX");
+}
+
+#[test]
+fn roman_numerals_with_vinculum() {
+    test_eval_simple("4000 as roman", "I\u{305}V\u{305}");
+    test_eval_simple("5000 as roman", "V\u{305}");
+    test_eval_simple("12345 as roman", "X\u{305}I\u{305}I\u{305}CCCXLV");
+    test_eval_simple("1000000 as roman", "M\u{305}");
+    test_eval_simple("5000000 as roman", "V\u{305}\u{305}");
+    test_eval_simple("2002000 as roman", "M\u{305}M\u{305}I\u{305}I\u{305}");
+    test_eval_simple("5005000 as roman", "V\u{305}\u{305}V\u{305}");
+    test_eval_simple(
+        "3999999999 as roman",
+        "M\u{305}\u{305}M\u{305}\u{305}M\u{305}\u{305}C\u{305}\u{305}M\u{305}\u{305}X\u{305}\u{305}C\u{305}\u{305}I\u{305}\u{305}X\u{305}\u{305}\
+         C\u{305}M\u{305}X\u{305}C\u{305}I\u{305}X\u{305}CMXCIX",
+    );
+}
+
+#[test]
+fn invalid_roman_numerals() {
+    expect_error(
+        "0 as roman",
+        Some("zero cannot be represented as a roman numeral"),
+    );
+    expect_error(
+        "4000000000 as roman",
+        Some("roman numerals are only supported for values up to 3999999999"),
+    );
+    expect_error("1.5 as roman", Some("cannot convert fraction to integer"));
+}
+
+#[test]
+fn version_info() {
+    test_eval("major of version_info", "0");
+    test_eval("minor of version_info", "1");
+    test_eval("patch of version_info", "14");
+    test_eval_simple("pre_release of version_info", "");
+}
+
+#[test]
+fn version_info_matches_version() {
+    let mut context = Context::new();
+    let mut eval = |input: &str| {
+        evaluate(input, &mut context)
+            .unwrap()
+            .get_main_result()
+            .to_string()
+    };
+    let components = format!(
+        "{}.{}.{}",
+        eval("major of version_info"),
+        eval("minor of version_info"),
+        eval("patch of version_info")
+    );
+    assert_eq!(components, eval("version"));
+}
+
+#[test]
+fn electron_properties() {
+    test_eval(
+        "charge of electron",
+        "-0.0000000000000000001602176634 coulomb",
+    );
+    test_eval("spin of electron", "0.5");
+    test_eval(
+        "magnetic_moment of electron",
+        "-0.0000000000000000000000092847647043 J / T",
+    );
+}
+
+#[test]
+fn proton_and_neutron_properties() {
+    test_eval("charge of proton", "0.0000000000000000001602176634 coulomb");
+    test_eval("charge of neutron", "0 coulomb");
+    test_eval("mass of proton / mass of electron", "approx. 1836.15267344");
+}
+
+#[test]
+fn series_e() {
+    test_eval("series_e(0)", "0");
+    test_eval("series_e(1)", "1");
+    test_eval("series_e(3)", "2.5");
+    test_eval_simple("series_e(10) as fraction", "98641/36288");
+    test_eval("series_e(20)", "approx. 2.7182818284");
+}
+
+#[test]
+fn series_e_with_fraction() {
+    expect_error("series_e(1.5)", Some("cannot convert fraction to integer"));
+}
+
+#[test]
+fn help_for_built_in_function() {
+    test_eval_simple("help(sin)", "sin(x): sine of angle x (in radians)");
+    test_eval_simple("help sqrt", "sqrt(x): square root of x");
+}
+
+#[test]
+fn help_for_constants_and_units() {
+    test_eval_simple("help(c)", "c: speed of light in vacuum (exact)");
+    test_eval_simple("help(feet)", "foot: defined as 12 inch");
+    test_eval_simple("help(meter)", "meter: base unit");
+}
+
+#[test]
+fn help_for_unknown_identifier() {
+    expect_error("help(blah)", Some("no help available for 'blah'"));
+    expect_error(
+        "help(1 + 1)",
+        Some("expected the name of a function or unit"),
+    );
+}
+
+#[test]
+fn to_fixed() {
+    test_eval_simple("to_fixed(3.14159, 2)", "3.14");
+    test_eval_simple("to_fixed(1.5, 2)", "1.50");
+    test_eval_simple("to_fixed(2, 0)", "2");
+    test_eval_simple("to_fixed(-1.005, 2)", "-1.01");
+    test_eval_simple("to_fixed(2.5 kg, 0)", "3 kg");
+    expect_error("to_fixed(1.5)", Some("expected 2 arguments"));
+}
+
+#[test]
+fn to_precision() {
+    test_eval_simple("to_precision(3.14159, 4)", "3.142");
+    test_eval_simple("to_precision(0.000123456, 2)", "0.00012");
+    test_eval_simple("to_precision(1.5, 4)", "1.500");
+    expect_error("to_precision(1.5, 2, 3)", Some("expected 2 arguments"));
+    expect_error(
+        "to_precision(1.5, 0)",
+        Some("cannot format a number with zero significant figures"),
+    );
+}
+
+#[test]
+fn arguments_without_spaces() {
+    test_eval_simple("to_fixed(3.14159,2)", "3.14");
+    test_eval_simple("to_precision(3.14159,4)", "3.142");
+    test_eval_simple("to_fixed(1,234.5,2)", "1234.50");
+    test_eval_simple("nth(1,(3,4,5))", "3");
+    test_eval_simple("range(1,4)", "(1, 2, 3)");
+    test_eval_simple("sum(1,2,3)", "6");
+    test_eval_simple("map(x: x*2,(1,2,3))", "(2, 4, 6)");
+    test_eval_simple("apply_at(sqrt,2,16)", "2");
+    test_eval_simple("bernstein(2,1,0.5)", "0.5");
+}
+
+#[test]
+fn lambda_with_multiple_arguments() {
+    test_eval("(x: y: x + y)(1, 2)", "3");
+    test_eval("(a: b: c: a * b - c)(2, 3, 4)", "2");
+}
+
+#[test]
+fn comma_separated_list() {
+    test_eval_simple("1, 2", "(1, 2)");
+}
+
+#[test]
+fn num_digits() {
+    test_eval("num_digits(255, 16)", "2");
+    test_eval("num_digits(1000000, 10)", "7");
+    test_eval("num_digits(0, 2)", "1");
+    test_eval("num_digits(2^100, 2)", "101");
+}
+
+#[test]
+fn num_digits_with_invalid_arguments() {
+    expect_error(
+        "num_digits(1.5, 10)",
+        Some("cannot convert fraction to integer"),
+    );
+    expect_error(
+        "num_digits(-5, 10)",
+        Some("negative numbers are not allowed"),
+    );
+    expect_error("num_digits(5, 1)", Some("base must be at least 2"));
+}
+
+#[test]
+fn reverse_digits() {
+    test_eval("reverse_digits(1234, 10)", "4321");
+    test_eval("reverse_digits(0b1010, 2)", "0b101");
+    test_eval("reverse_digits(1200, 10)", "21");
+    test_eval("reverse_digits(0, 10)", "0");
+    test_eval("reverse_digits(0xff0, 16)", "0xff");
+}
+
+#[test]
+fn reverse_digits_of_fraction() {
+    expect_error(
+        "reverse_digits(1.5, 10)",
+        Some("cannot convert fraction to integer"),
+    );
+}
+
+#[test]
+fn to_roman_function() {
+    test_eval_simple("to_roman(42)", "XLII");
+    test_eval_simple("to_roman(1994)", "MCMXCIV");
+    expect_error(
+        "to_roman(0)",
+        Some("zero cannot be represented as a roman numeral"),
+    );
+}
+
+#[test]
+fn from_roman_function() {
+    test_eval(r#"from_roman("XLII")"#, "42");
+    test_eval(r#"from_roman("mcmxciv")"#, "1994");
+    test_eval(r#"from_roman("MMMCMXCIX")"#, "3999");
+    test_eval(r#"from_roman(to_roman(2021))"#, "2021");
+}
+
+#[test]
+fn from_roman_with_invalid_numerals() {
+    expect_error(r#"from_roman("IIII")"#, Some("invalid roman numeral"));
+    expect_error(r#"from_roman("IC")"#, Some("invalid roman numeral"));
+    expect_error(r#"from_roman("ABC")"#, Some("invalid roman numeral"));
+    expect_error(r#"from_roman("")"#, Some("invalid roman numeral"));
+    expect_error("from_roman(5)", Some("expected a string"));
+}
+
+#[test]
+fn binary_coded_decimal() {
+    test_eval("bcd(123)", "0x123");
+    test_eval("to_bcd(9876543210)", "0x9876543210");
+    test_eval("bcd(0)", "0x0");
+    test_eval("from_bcd(0x123)", "123");
+    test_eval("from_bcd(bcd(2021))", "2021");
+}
+
+#[test]
+fn invalid_binary_coded_decimal() {
+    expect_error(
+        "from_bcd(0x1a)",
+        Some("number is not valid binary-coded decimal"),
+    );
+    expect_error("bcd(-1)", Some("negative numbers are not allowed"));
+}
+
+#[test]
+fn interleave_bits() {
+    test_eval("interleave(0b1010, 0b1100)", "0xe4");
+    test_eval("interleave(0, 0)", "0x0");
+    test_eval("interleave(1, 0)", "0x1");
+    test_eval("interleave(0, 1)", "0x2");
+    test_eval("interleave(0xffff, 0) to decimal", "1431655765");
+    expect_error(
+        "interleave(1.5, 1)",
+        Some("cannot convert fraction to integer"),
+    );
+}
+
+#[test]
+fn max_output_length() {
+    let mut context = Context::new();
+    context.set_max_output_length(10);
+    let result = evaluate("100!", &mut context).unwrap();
+    assert_eq!(result.get_main_result(), "9332621544...");
+    assert!(result.is_truncated());
+    let result = evaluate("5 kg", &mut context).unwrap();
+    assert_eq!(result.get_main_result(), "5 kg");
+    assert!(!result.is_truncated());
+}
+
+#[test]
+fn max_output_length_at_span_boundary() {
+    let mut context = Context::new();
+    context.set_max_output_length(5);
+    let result = evaluate("12345 kg", &mut context).unwrap();
+    assert_eq!(result.get_main_result(), "12345...");
+    assert!(result.is_truncated());
+}
+
+#[test]
+fn max_output_length_keeps_units_intact() {
+    let mut context = Context::new();
+    context.set_max_output_length(7);
+    let result = evaluate("12345 kg", &mut context).unwrap();
+    assert_eq!(result.get_main_result(), "12345...");
+    assert!(result.is_truncated());
+}
+
+#[test]
+fn max_output_length_with_multi_byte_characters() {
+    let mut context = Context::new();
+    context.set_max_output_length(3);
+    let result = evaluate("\"äöüß\"", &mut context).unwrap();
+    assert_eq!(result.get_main_result(), "äöü...");
+    assert!(result.is_truncated());
+}
+
+#[test]
+fn max_output_length_can_be_removed() {
+    let mut context = Context::new();
+    context.set_max_output_length(3);
+    assert_eq!(
+        evaluate("12345", &mut context).unwrap().get_main_result(),
+        "123..."
+    );
+    context.clear_max_output_length();
+    let result = evaluate("12345", &mut context).unwrap();
+    assert_eq!(result.get_main_result(), "12345");
+    assert!(!result.is_truncated());
+}
+
+#[test]
+fn currency_format() {
+    test_eval_simple("1234.5 USD as currency", "$1,234.50");
+    test_eval_simple("5 dollars as currency", "$5.00");
+    test_eval_simple("-1234567.891 EUR to currency", "-\u{20ac}1,234,567.89");
+    test_eval_simple("3 GBP as currency", "\u{a3}3.00");
+    test_eval_simple("12 CHF as currency", "CHF 12.00");
+    test_eval_simple("1234.5 as currency", "1,234.50");
+    test_eval_simple("1.005 USD as currency", "$1.01");
+    test_eval_simple("-0.001 USD as currency", "$0.00");
+}
+
+#[test]
+fn currency_format_with_other_units() {
+    expect_error(
+        "5 kg as currency",
+        Some("only amounts of money can be formatted as currency"),
+    );
+    expect_error(
+        "i USD as currency",
+        Some("cannot format a complex number as currency"),
+    );
+}
+
+#[track_caller]
+fn test_latex(input: &str, expected: Option<&str>) {
+    let mut context = Context::new();
+    let result = evaluate(input, &mut context).unwrap();
+    assert_eq!(result.to_latex().as_deref(), expected);
+}
+
+#[test]
+fn latex_output() {
+    test_latex("1/3 as fraction", Some(r"\frac{1}{3}"));
+    test_latex("4/3 as mixed_fraction", Some(r"1\frac{1}{3}"));
+    test_latex("1/3 as float", Some(r"0.\overline{3}"));
+    test_latex("pi", Some(r"\approx 3.1415926535"));
+    test_latex("3 m^2", Some(r"3\,\mathrm{m}^{2}"));
+    test_latex(
+        "1 kg m^2 / s",
+        Some(r"1\,\mathrm{kg}\,\mathrm{m}^{2}/\mathrm{s}"),
+    );
+    test_latex("1 + 2i", Some("1 + 2i"));
+    test_latex("\"a_b\"", Some(r"\text{a\_b}"));
+}
+
+#[test]
+fn latex_output_for_functions() {
+    test_latex("sqrt", None);
+    test_latex("x: x", None);
+    test_latex("", None);
+}
+
+#[track_caller]
+fn test_rational_approximation(input: &str, expected: Option<(&str, &str)>) {
+    let mut context = Context::new();
+    let result = evaluate(input, &mut context).unwrap();
+    assert_eq!(
+        result.get_rational_approximation(),
+        expected.map(|(a, b)| (a.to_string(), b.to_string()))
+    );
+}
+
+#[test]
+fn rational_approximation() {
+    test_rational_approximation("1/3", Some(("1", "3")));
+    test_rational_approximation("-0.75", Some(("-3", "4")));
+    test_rational_approximation("5", Some(("5", "1")));
+    test_rational_approximation("2/4 kg", Some(("1", "2")));
+    test_rational_approximation("2^64 / 3", Some(("18446744073709551616", "3")));
+}
+
+#[test]
+fn rational_approximation_of_irrational_values() {
+    test_rational_approximation("pi", None);
+    test_rational_approximation("2 + i", None);
+    test_rational_approximation("\"a\"", None);
+}
+
+#[test]
+fn degrees_as_dms() {
+    test_eval_simple("1.5 degrees as dms", "1°30′0″");
+    test_eval_simple("-1.5 degrees as dms", "-1°30′0″");
+    test_eval_simple("1°30′15.5″ as dms", "1°30′15.5″");
+    test_eval_simple("pi/2 radians as dms", "90°0′0″");
+}
+
+#[test]
+fn dms_to_degrees() {
+    test_eval("1°30′ as degrees", "1.5 degrees");
+    test_eval("1°30′", "1.5°");
+    test_eval("30′ to degrees", "0.5 degrees");
+}
+
+#[test]
+fn dms_with_other_units() {
+    expect_error(
+        "2 kg as dms",
+        Some("only angles can be formatted in degrees, minutes and seconds"),
+    );
+    expect_error(
+        "i degrees as dms",
+        Some("cannot format a complex number in degrees, minutes and seconds"),
+    );
+}
+
+#[test]
+fn seconds_as_hms() {
+    test_eval_simple("3661.5 seconds as hms", "1h 1m 1.5s");
+    test_eval_simple("1.5 hours as hms", "1h 30m 0s");
+    test_eval_simple("90000 s as hms", "25h 0m 0s");
+    test_eval_simple("-90 minutes as hms", "-1h 30m 0s");
+}
+
+#[test]
+fn hms_with_other_units() {
+    expect_error(
+        "2 kg as hms",
+        Some("only durations can be formatted in hours, minutes and seconds"),
+    );
+}
+
+#[test]
+fn nato_phonetic_alphabet() {
+    test_eval_simple("\"fend\" as NATO", "Foxtrot Echo November Delta");
+    test_eval_simple("\"v1.5\" to nato", "Victor One Decimal Five");
+    test_eval_simple("\"a-b c\" as NATO", "Alfa - Bravo Charlie");
+    expect_error("5 as NATO", Some("expected a string"));
+}
+
+#[test]
+fn from_nato_phonetic_alphabet() {
+    test_eval_simple("from_NATO(\"Foxtrot Echo November Delta\")", "FEND");
+    test_eval_simple("from_nato(\"alpha juliet niner decimal one\")", "AJ9.1");
+}
+
+#[test]
+fn hex_dump_of_string() {
+    test_eval_simple("hex_dump(\"ABC\")", "41 42 43");
+    test_eval_simple("hex_dump(\"\u{e9}\")", "c3 a9");
+    test_eval_simple("hex_dump(\"\")", "");
+}
+
+#[test]
+fn hex_to_string() {
+    test_eval_simple("hex_to_string(\"41 42 43\")", "ABC");
+    test_eval_simple("hex_to_string(\"c3A9\")", "\u{e9}");
+    expect_error(
+        "hex_to_string(\"414\")",
+        Some("expected an even number of hex digits"),
+    );
+    expect_error("hex_to_string(\"+f\")", Some("invalid hex byte '+f'"));
+    expect_error("hex_to_string(\"ff\")", Some("bytes are not valid UTF-8"));
+}
+
+#[test]
+fn crc32_checksum() {
+    test_eval_simple("crc32(\"hello\")", "0x3610a686");
+    test_eval_simple("crc32(\"123456789\")", "0xcbf43926");
+    test_eval_simple("crc32(\"\")", "0x0");
+}
+
+#[test]
+fn crc16_checksum() {
+    test_eval_simple("crc16(\"hello\")", "0x34d2");
+    test_eval_simple("crc16(\"123456789\")", "0xbb3d");
+    expect_error("crc16(5)", Some("expected a string"));
+}
+
+#[test]
+fn djb2_hash() {
+    test_eval_simple("djb2(\"hello\")", "210714636441");
+    test_eval_simple("djb2(\"\")", "5381");
+}
+
+#[test]
+fn fnv1a_hash() {
+    test_eval_simple("fnv1a(\"hello\")", "11831194018420276491");
+    test_eval_simple("fnv1a(\"\")", "14695981039346656037");
+    expect_error("fnv1a(5)", Some("expected a string"));
+}
+
+#[test]
+fn unix_time_of_today() {
+    let mut context = Context::new();
+    context.set_current_time_v1(1617517099000, 0);
+    assert_eq!(
+        evaluate("unix_time(today)", &mut context)
+            .unwrap()
+            .get_main_result(),
+        "1617494400"
+    );
+}
+
+#[test]
+fn unix_time_of_dates() {
+    test_eval_simple("unix_time(\"1970-01-01\" as date)", "0");
+    test_eval_simple("unix_time(\"2000-03-01\" as date)", "951868800");
+    test_eval_simple("unix_time(\"1969-12-31\" as date)", "-86400");
+    expect_error("unix_time(5)", Some("expected a date"));
+}
+
+#[test]
+fn from_unix_time() {
+    test_eval_simple("from_unix_time(0)", "Thursday, 1 January 1970");
+    test_eval_simple("from_unix_time(1617517099)", "Sunday, 4 April 2021");
+    test_eval_simple("from_unix_time(-1)", "Wednesday, 31 December 1969");
+    test_eval_simple("from_unix_time(951868800)", "Wednesday, 1 March 2000");
+    expect_error("from_unix_time(10^17)", Some("date is out of range"));
+}
+
+#[test]
+fn day_of_year() {
+    test_eval("day_of_year of (\"2021-01-01\" as date)", "1");
+    test_eval("day_of_year of (\"2021-04-04\" as date)", "94");
+    test_eval("day_of_year of (\"2020-12-31\" as date)", "366");
+}
+
+#[test]
+fn days_in_month() {
+    test_eval("days_in_month of (\"2020-02-03\" as date)", "29");
+    test_eval("days_in_month of (\"2021-02-03\" as date)", "28");
+    test_eval("days_in_month of (\"2021-04-04\" as date)", "30");
+}
+
+#[test]
+fn days_in_year() {
+    test_eval("days_in_year of (\"2000-06-01\" as date)", "366");
+    test_eval("days_in_year of (\"1900-06-01\" as date)", "365");
+    expect_error(
+        "weeks of (\"2021-04-04\" as date)",
+        Some("could not find key in date"),
+    );
+}
+
+#[test]
+fn day_of_year_of_today() {
+    let mut context = Context::new();
+    context.set_current_time_v1(1617517099000, 0);
+    assert_eq!(
+        evaluate("day_of_year of today", &mut context)
+            .unwrap()
+            .get_main_result(),
+        "94"
+    );
+}
+
+#[test]
+fn lunar_phase() {
+    test_eval_simple("lunar_phase(\"2024-04-08\" as date)", "New Moon");
+    test_eval_simple("lunar_phase(\"2024-04-15\" as date)", "First Quarter");
+    test_eval_simple("lunar_phase(\"2024-04-19\" as date)", "Waxing Gibbous");
+    test_eval_simple("lunar_phase(\"2024-04-23\" as date)", "Full Moon");
+    test_eval_simple("lunar_phase(\"2024-05-01\" as date)", "Last Quarter");
+    expect_error("lunar_phase(5)", Some("expected a date"));
+}
+
+#[test]
+fn meteorological_season() {
+    test_eval_simple("season(\"2021-01-15\" as date)", "Winter");
+    test_eval_simple("season(\"2021-04-04\" as date)", "Spring");
+    test_eval_simple("season(\"2021-12-01\" as date, \"north\")", "Winter");
+    test_eval_simple("season(\"2021-07-15\" as date, \"south\")", "Winter");
+    test_eval_simple("season(\"2021-10-15\" as date, \"Southern\")", "Spring");
+    expect_error(
+        "season(\"2021-10-15\" as date, \"east\")",
+        Some("hemisphere must be \"north\" or \"south\""),
+    );
+}
+
+#[test]
+fn time_zone_offsets() {
+    test_eval("time_zone_offset(\"EST\")", "-5 hours");
+    test_eval("time_zone_offset(\"CET\")", "1 hour");
+    test_eval("time_zone_offset(\"ist\")", "5.5 hours");
+    test_eval("time_zone_offset(\"UTC\")", "0 hours");
+    test_eval(
+        "time_zone_offset(\"JST\") - time_zone_offset(\"PST\")",
+        "17 hours",
+    );
+    expect_error("time_zone_offset(\"XYZ\")", Some("unknown time zone 'XYZ'"));
+}
+
+#[test]
+fn human_readable_durations() {
+    test_eval("\"2h 30m 15s\" as duration", "9015 seconds");
+    test_eval("\"1d 2h 30m\" as duration", "95400 seconds");
+    test_eval("\"45 minutes\" as duration", "2700 seconds");
+    test_eval("\"3.5 hours\" as duration", "12600 seconds");
+    test_eval("\"1 day, 2 hours and 5 secs\" as duration", "93605 seconds");
+    test_eval("(\"2h 30m\" as duration) to hours", "2.5 hours");
+}
+
+#[test]
+fn invalid_durations() {
+    expect_error(
+        "\"1 fortnight\" as duration",
+        Some("unknown duration unit 'fortnight'"),
+    );
+    expect_error("\"5\" as duration", Some("missing unit in duration '5'"));
+    expect_error("\"\" as duration", Some("duration cannot be empty"));
+    expect_error("5 as duration", Some("expected a string"));
+}
+
+#[test]
+fn countdown_to_date() {
+    let mut context = Context::new();
+    context.set_current_time_v1(1617517099000, 0);
+    for (input, expected) in &[
+        ("countdown(\"2021-12-25\" as date)", "265 days"),
+        ("countdown(\"2021-04-05\" as date)", "1 day"),
+        ("countdown(today)", "0 days"),
+        ("countdown(\"2021-01-01\" as date)", "-93 days"),
+        ("countdown(\"2021-04-05\" as date) to hours", "24 hours"),
+    ] {
+        assert_eq!(
+            evaluate(input, &mut context).unwrap().get_main_result(),
+            *expected
+        );
+    }
+}
+
+#[test]
+fn countdown_without_current_date() {
+    expect_error(
+        "countdown(\"2021-12-25\" as date)",
+        Some("unable to get the current date"),
+    );
+}
+
+#[test]
+fn age_of_date() {
+    let mut context = Context::new();
+    context.set_current_time_v1(1617517099000, 0);
+    for (input, expected) in &[
+        ("age(\"1990-01-15\" as date)", "31 years, 2 months, 20 days"),
+        ("age(\"2020-04-04\" as date)", "1 year, 0 months, 0 days"),
+        ("age(\"2021-03-31\" as date)", "0 years, 0 months, 4 days"),
+        ("age(\"2021-01-31\" as date)", "0 years, 2 months, 4 days"),
+        ("age(\"2000-02-29\" as date)", "21 years, 1 month, 6 days"),
+        ("age(today)", "0 years, 0 months, 0 days"),
+    ] {
+        assert_eq!(
+            evaluate(input, &mut context).unwrap().get_main_result(),
+            *expected
+        );
+    }
+    assert_eq!(
+        evaluate("age(\"2022-01-01\" as date)", &mut context)
+            .err()
+            .unwrap(),
+        "date is in the future"
+    );
+}
+
+#[test]
+fn format_number_with_template() {
+    test_eval_simple("format_number(1234567.89, \"#,##0.00\")", "1,234,567.89");
+    test_eval_simple("format_number(1234.5, \"$#,##0.00\")", "$1,234.50");
+    test_eval_simple("format_number(-1234.567, \"#,##0.##\")", "-1,234.57");
+    test_eval_simple("format_number(1234, \"#,##0.##\")", "1,234");
+    test_eval_simple("format_number(5, \"000\")", "005");
+    test_eval_simple("format_number(2/3, \"0.0000\")", "0.6667");
+    test_eval_simple("format_number(12, \"0.# kg\")", "12 kg");
+}
+
+#[test]
+fn invalid_number_format_templates() {
+    expect_error(
+        "format_number(1, \"abc\")",
+        Some("number format template must contain '#' or '0'"),
+    );
+    expect_error(
+        "format_number(1, \"0.0.0\")",
+        Some("invalid decimal places in number format template"),
+    );
+}
+
+#[test]
+fn approximate_decimals_as_fractions() {
+    test_eval_simple("2.333 as mixed_fraction", "2 333/1000");
+    test_eval_simple("approx. 2.333 as mixed_fraction", "approx. 2 1/3");
+    test_eval_simple("approx. -2.333 as mixed_fraction", "approx. -2 1/3");
+    test_eval_simple("approx. 0.667 as fraction", "approx. 2/3");
+    test_eval_simple("approx. 0.1 as fraction", "approx. 1/10");
+    test_eval_simple("approx. 3.14159 as mixed_fraction", "approx. 3 16/113");
+}
+
+#[test]
+fn nth_element_of_list() {
+    test_eval("nth(1, (3, 4, 5))", "3");
+    test_eval("nth(3, (3, 4, 5 kg))", "5 kg");
+    test_eval_simple("nth(2, (\"a\", \"b\"))", "b");
+    test_eval_simple("nth(2, (1, (2, 3)))", "(2, 3)");
+}
+
+#[test]
+fn nth_out_of_range() {
+    expect_error(
+        "nth(0, (3, 4))",
+        Some("index 0 is out of range for a list of length 2"),
+    );
+    expect_error(
+        "nth(4, (3, 4, 5))",
+        Some("index 4 is out of range for a list of length 3"),
+    );
+    expect_error("nth(1, 5)", Some("expected a list"));
+}
+
+#[test]
+fn zip_lists() {
+    test_eval_simple("zip(add, (1, 2, 3), (4, 5, 6))", "(5, 7, 9)");
+    test_eval_simple("zip((a: b: a * b), (1, 2), (3, 4))", "(3, 8)");
+    test_eval("add(1 kg, 2 kg)", "3 kg");
+}
+
+#[test]
+fn zip_lists_of_different_lengths() {
+    expect_error(
+        "zip(add, (1, 2, 3), (4, 5))",
+        Some("cannot zip lists of different lengths (3 and 2)"),
+    );
+}
+
+#[test]
+fn map_list() {
+    test_eval_simple("map(x: x^2, (1, 2, 3, 4))", "(1, 4, 9, 16)");
+    test_eval_simple("map(abs, (-1, 2, -3))", "(1, 2, 3)");
+    test_eval_simple("map(x: x to g, (1 kg, 2 kg))", "(1000 g, 2000 g)");
+    expect_error("map(abs, 5)", Some("expected a list"));
+}
+
+#[test]
+fn comparison_operators() {
+    test_eval("2 < 3", "1");
+    test_eval("3 < 3", "0");
+    test_eval("3 <= 3", "1");
+    test_eval("4 > 3", "1");
+    test_eval("3 >= 4", "0");
+    test_eval("1 == 1", "1");
+    test_eval("1 != 1", "0");
+    test_eval("2 kg > 1500 g", "1");
+    test_eval("3! == 6", "1");
+}
+
+#[test]
+fn invalid_comparisons() {
+    expect_error("1 m < 1 s", Some("units are incompatible"));
+    expect_error("i > 1", Some("cannot compare complex numbers"));
+    expect_error("1 < 2 < 3", Some("unexpected input found"));
+}
+
+#[test]
+fn filter_list() {
+    test_eval_simple("filter(x: x > 3, (1, 2, 3, 4, 5))", "(4, 5)");
+    test_eval_simple("filter(x: x != 2, (1, 2, 3))", "(1, 3)");
+    test_eval_simple("filter(x: x - 1, (1, 2, 1, 3))", "(2, 3)");
+}
+
+#[test]
+fn fold_list() {
+    test_eval("fold(add, 0, (1, 2, 3, 4))", "10");
+    test_eval("fold((a: b: a * b), 1, (1, 2, 3, 4))", "24");
+    test_eval("reduce((a: b: a - b), 10, (1, 2))", "7");
+    expect_error("fold(add, 0)", Some("expected 3 arguments"));
+}
+
+#[test]
+fn range_lists() {
+    test_eval_simple("range(1, 10, 2)", "(1, 3, 5, 7, 9)");
+    test_eval_simple("range(1, 5)", "(1, 2, 3, 4)");
+    test_eval_simple("range(5, 1, -2)", "(5, 3)");
+    test_eval_simple("range(0 m, 3 m, 1 m)", "(0 m, 1 m, 2 m)");
+    test_eval_simple("range(3, 3)", "()");
+    expect_error("range(1, 2, 0)", Some("step of a range cannot be zero"));
+}
+
+#[test]
+fn sum_of_range() {
+    test_eval("sum(range(1, 100))", "4950");
+    test_eval("sum((1 kg, 500 g))", "1.5 kg");
+}
+
+#[test]
+fn tabulate_function() {
+    test_eval_simple(
+        "tabulate(x: x^2, 8, 11)",
+        "8  | 64\n9  | 81\n10 | 100\n11 | 121",
+    );
+    test_eval_simple(
+        "tabulate(sin, 0 degrees, 100 degrees, 30 degrees)",
+        "0 degrees  | 0\n30 degrees | 0.5\n60 degrees | approx. 0.8660254037\n90 degrees | 1",
+    );
+    test_eval_simple(
+        "tabulate(sin, 0, 100 degrees, 30 degrees)",
+        "0 degrees  | 0\n30 degrees | 0.5\n60 degrees | approx. 0.8660254037\n90 degrees | 1",
+    );
+    test_eval_simple(
+        "tabulate(cos, 0, pi, 45 degrees)",
+        "0 degrees   | 1\n45 degrees  | approx. 0.7071067811\n90 degrees  | 0\n135 degrees | approx. -0.7071067811\n180 degrees | -1",
+    );
+    let table = evaluate(
+        "tabulate(sin, 0, 360 degrees, 30 degrees)",
+        &mut Context::new(),
+    )
+    .unwrap()
+    .get_main_result()
+    .to_string();
+    assert_eq!(table.lines().count(), 13);
+    assert_eq!(table.lines().last(), Some("360 degrees | 0"));
+    test_eval_simple("tabulate(x: x, 1, 0, -0.5)", "1   | 1\n0.5 | 0.5\n0   | 0");
+    expect_error("tabulate(sin, 1)", Some("expected 3 or 4 arguments"));
+}
+
+#[test]
+fn histogram_of_list() {
+    test_eval_simple("histogram((1, 2, 2, 3, 3, 3, 4), 3)", "(1, 2, 4)");
+    test_eval_simple("histogram((1, 2, 2, 3, 3, 3, 4), 1)", "(7)");
+    test_eval_simple("histogram((1 m, 50 cm, 2 m), 2)", "(2, 1)");
+    test_eval_simple("histogram((5, 5, 5), 3)", "(0, 3, 0)");
+    test_eval_simple("histogram((5, 5), 1)", "(2)");
+    test_eval_simple("histogram((1 m, 100 cm), 4)", "(0, 0, 2, 0)");
+    expect_error(
+        "histogram((1, 2), 0)",
+        Some("number of bins must be positive"),
+    );
+}
+
+#[test]
+fn mean_and_median() {
+    test_eval("mean((1, 2, 3, 4, 5))", "3");
+    test_eval("mean((1 kg, 500 g))", "0.75 kg");
+    test_eval("median((5, 1, 3))", "3");
+    test_eval("median((4, 1, 3, 2))", "2.5");
+    expect_error(
+        "mean(filter(x: x > 5, (1, 2)))",
+        Some("expected a non-empty list"),
+    );
+}
+
+#[test]
+fn variance_and_stddev() {
+    test_eval("variance((1, 2, 3, 4, 5))", "2");
+    test_eval("variance_sample((1, 2, 3, 4, 5))", "2.5");
+    test_eval("variance((1 m, 3 m))", "1 m^2");
+    test_eval("stddev((1, 2, 3, 4, 5))", "approx. 1.4142135619");
+    test_eval("stddev_sample((2 m, 4 m))", "approx. 1.4142135619 m");
+    expect_error(
+        "stddev_sample(filter(x: x > 1, (1, 2)))",
+        Some("sample variance requires at least 2 values"),
+    );
+}
+
+#[test]
+fn format_with_uncertainty() {
+    test_eval_simple("format_with_uncertainty(9.8, 0.1)", "9.8 ± 0.1");
+    test_eval_simple("format_with_uncertainty(9.8123, 0.03)", "9.81 ± 0.03");
+    test_eval_simple("format_with_uncertainty(1234, 20)", "1234 ± 20");
+    test_eval_simple("format_with_uncertainty(1.5 m, 2 cm)", "1.50 m ± 0.02 m");
+    expect_error(
+        "format_with_uncertainty(1, 0)",
+        Some("uncertainty must be positive"),
+    );
+}
+
+#[test]
+fn plus_minus_operator() {
+    test_eval_simple("9.8 ± 0.1", "9.8 ± 0.1");
+    test_eval_simple("(9.8 ± 0.1) + (3.2 ± 0.05)", "13 ± 0.15");
+    test_eval_simple("(5 ± 1) - 3", "2 ± 1");
+    test_eval_simple("(1 m ± 1 cm) to cm", "100 cm ± 1 cm");
+}
+
+#[test]
+fn plus_minus_multiplication() {
+    test_eval_simple("(10 ± 1) * (2 ± 0.1)", "20 ± 3");
+    test_eval_simple("(10 ± 1) / 2", "5 ± 0.5");
+    test_eval_simple("2 * (3 ± 0.5)", "6 ± 1");
+    expect_error("1 ± -1", Some("uncertainty cannot be negative"));
+}
+
+#[test]
+fn plus_minus_with_units() {
+    test_eval_simple("(1 m ± 1 m) + 1 m", "2 m ± 1 m");
+    test_eval_simple("1 kg + (2 kg ± 0.5 kg)", "3 kg ± 0.5 kg");
+}
+
+#[test]
+fn plus_minus_with_attached_units() {
+    test_eval_simple("(10 ± 1) m", "10 m ± 1 m");
+    test_eval_simple("(10 ± 1) * 2 m", "20 m ± 2 m");
+    test_eval_simple("(10 ± 1) kg m / s^2", "10 kg m / s^2 ± 1 kg m / s^2");
+    test_eval_simple("(10 ± 1) m to cm", "1000 cm ± 100 cm");
+    test_eval_simple("3 (10 ± 1)", "30 ± 3");
+}
+
+#[test]
+fn plus_minus_negation() {
+    test_eval_simple("-(2 ± 0.1)", "-2 ± 0.1");
+    test_eval_simple("5 - (2 ± 0.1)", "3 ± 0.1");
+}
+
+#[test]
+fn plus_minus_powers() {
+    test_eval_simple("(2 ± 0.1)^2", "4 ± 0.4");
+    test_eval_simple("(4 ± 0.4)^0.5", "2 ± 0.1");
+    test_eval_simple("(2 ± 0.1)^-1", "0.5 ± 0.025");
+    test_eval_simple("(2 m ± 1 cm)^2", "4 m^2 ± 0.04 m^2");
+    expect_error(
+        "2^(1 ± 0.1)",
+        Some("this operation is not supported for numbers with an uncertainty"),
+    );
+    expect_error(
+        "(0 ± 0.1)^2",
+        Some("cannot raise a measurement of zero to a power"),
+    );
+}
+
+#[test]
+fn plus_minus_formatting() {
+    test_eval_simple("(1.2345 ± 0.1) to 2 dp", "approx. 1.23 ± 0.1");
+    test_eval_simple("(10 ± 1) to hex", "a ± 1");
+    expect_error(
+        "sin(1 ± 0.1)",
+        Some("this operation is not supported for numbers with an uncertainty"),
+    );
+}
+
+#[test]
+fn bitcount() {
+    test_eval_simple("bitcount(0b10110)", "3");
+    test_eval_simple("popcount(0)", "0");
+    test_eval_simple("bitcount(2^100 - 1)", "100");
+    expect_error("bitcount(1.5)", None);
+}
+
+#[test]
+fn count_leading_zeros() {
+    test_eval_simple("clz(0b10000, 8)", "3");
+    test_eval_simple("clz(1, 4)", "3");
+    test_eval_simple("clz(0, 64)", "64");
+    expect_error("clz(256, 8)", Some("256 does not fit into 8 bits"));
+    expect_error("clz(1, 0)", Some("bit width must be between 1 and 64"));
+}
+
+#[test]
+fn swap_bytes() {
+    test_eval("swap_bytes(0x12345678, 4)", "0x78563412");
+    test_eval("swap_bytes(0x1234, 2)", "0x3412");
+    test_eval_simple("swap_bytes(258, 2)", "513");
+    test_eval_simple("swap_bytes(0xab, 1)", "0xab");
+    expect_error(
+        "swap_bytes(1, 3)",
+        Some("number of bytes must be 1, 2, 4 or 8"),
+    );
+    expect_error("swap_bytes(256, 1)", Some("256 does not fit into 8 bits"));
+}
+
+#[test]
+fn rotate_bits() {
+    test_eval("rotate_left(0b10110001, 3, 8)", "0b10001101");
+    test_eval("rotate_right(0b10001101, 3, 8)", "0b10110001");
+    test_eval("rotate_left(0x12, 4, 8)", "0x21");
+    test_eval_simple("rotate_left(5, 16, 8)", "5");
+    test_eval_simple("rotate_right(1, 1, 64)", "9223372036854775808");
+    expect_error(
+        "rotate_left(256, 1, 8)",
+        Some("256 does not fit into 8 bits"),
+    );
+    expect_error("rotate_left(1, 1)", Some("expected 3 arguments"));
+}
+
+#[test]
+fn gray_code() {
+    test_eval_simple("gray_code(6)", "5");
+    test_eval_simple("from_gray(5)", "6");
+    test_eval("gray_code(0b1000)", "0b1100");
+    test_eval_simple("from_gray(gray_code(123456789))", "123456789");
+    expect_error("gray_code(-1)", None);
+}
+
+#[test]
+fn hamming_distance() {
+    test_eval_simple("hamming_distance(0b1010, 0b1100)", "2");
+    test_eval_simple("hamming_distance(7, 7)", "0");
+    test_eval_simple("hamming_distance(0, 0xff)", "8");
+    expect_error("hamming_distance(1)", Some("expected 2 arguments"));
+}
+
+#[test]
+fn unary_numbers() {
+    test_eval_simple("to_unary(5)", "IIIII");
+    test_eval_simple("to_unary(7)", "IIIII II");
+    test_eval_simple("to_unary(0)", "");
+    test_eval_simple(r#"from_unary("IIII")"#, "4");
+    test_eval_simple("from_unary(to_unary(12))", "12");
+    expect_error(
+        r#"from_unary("IIX")"#,
+        Some("invalid character 'X' in unary number"),
+    );
+}
+
+#[test]
+fn braille_numbers() {
+    test_eval_simple("to_braille_number(42)", "⠼⠙⠃");
+    test_eval_simple("to_braille_number(1234567890)", "⠼⠁⠃⠉⠙⠑⠋⠛⠓⠊⠚");
+    test_eval_simple(r#"from_braille_number("⠼⠙⠃")"#, "42");
+    test_eval_simple("from_braille_number(to_braille_number(0))", "0");
+    expect_error(
+        r#"from_braille_number("⠙⠃")"#,
+        Some("Braille numbers must start with the number indicator"),
+    );
+    expect_error(
+        r#"from_braille_number("⠼⠙x")"#,
+        Some("invalid Braille digit 'x'"),
+    );
+}
+
+#[test]
+fn lcm_range() {
+    test_eval("lcm_range(6)", "60");
+    test_eval("lcm_range(0)", "1");
+    test_eval("lcm_range(20)", "232792560");
+    test_eval(
+        "lcm_range(100)",
+        "69720375229712477164533808935312303556800",
+    );
+    expect_error("lcm_range(-1)", None);
+}
+
+#[test]
+fn primorial() {
+    test_eval("primorial(10)", "210");
+    test_eval("primorial(0)", "1");
+    test_eval("primorial(1)", "1");
+    test_eval("primorial(13)", "30030");
+    test_eval("primorial(50)", "614889782588491410");
+}
+
+#[test]
+fn collatz() {
+    test_eval("collatz(27)", "111");
+    test_eval("collatz(1)", "0");
+    test_eval("collatz(6)", "8");
+    test_eval("collatz(2^100)", "100");
+    expect_error("collatz(0)", Some("collatz requires a positive integer"));
+}
+
+#[test]
+fn taxi_distance() {
+    test_eval("taxi_distance(1, 1, 4, 5)", "7");
+    test_eval("manhattan_distance(4, 5, 1, 1)", "7");
+    test_eval("taxi_distance(1 km, 0 km, 2 km, 500 m)", "1.5 km");
+    expect_error(
+        "taxi_distance(1 kg, 0, 2, 3)",
+        Some("units are incompatible"),
+    );
+    expect_error("taxi_distance(1, 2, 3)", Some("expected 4 arguments"));
+}
+
+#[test]
+fn chebyshev_distance() {
+    test_eval("chebyshev_distance(1, 1, 4, 5)", "4");
+    test_eval("chebyshev_distance(0 m, 0 m, 1 m, 2 km)", "2000 m");
+    test_eval("chebyshev_distance(-3, 0, 3, 0)", "6");
+}
+
+#[test]
+fn haversine() {
+    test_eval_simple(
+        "haversine(51.5°, -0.12°, 48.85°, 2.35°) to km",
+        "approx. 343.1278778235 km",
+    );
+    test_eval_simple(
+        "haversine(0°, 0°, 0°, 180°) to km",
+        "approx. 20015.0867960205 km",
+    );
+    test_eval_simple("haversine(10°, 20°, 10°, 20°)", "approx. 0 m");
+    test_eval_simple(
+        "haversine(51.5, -0.12, 40.7, -74) to km",
+        "approx. 5571.490771963 km",
+    );
+    test_eval_simple(
+        "haversine(51.5°, -0.12°, 40.7°, -74°) to km",
+        "approx. 5571.490771963 km",
+    );
+    test_eval_simple(
+        "haversine(0.9 radians, 0, 0.9 radians, 1) to km",
+        "approx. 69.1193365082 km",
+    );
+    expect_error("haversine(1°, 2°)", Some("expected 4 arguments"));
+}
+
+#[test]
+fn compass_bearing() {
+    test_eval_simple(
+        "compass_bearing(51.5°, -0.12°, 48.85°, 2.35°)",
+        "approx. 148.2102556484 degrees",
+    );
+    test_eval_simple("compass_bearing(0°, 0°, 0°, 1°)", "90 degrees");
+    test_eval_simple("compass_bearing(0°, 0°, -1°, 0°)", "approx. 180 degrees");
+    test_eval_simple("compass_bearing(0°, 0°, 0°, -1°)", "270 degrees");
+    test_eval_simple(
+        "compass_bearing(51.5, -0.12, 40.7, -74)",
+        "approx. 288.3236838862 degrees",
+    );
+    test_eval_simple(
+        "compass_bearing(51.5°, -0.12°, 40.7°, -74°)",
+        "approx. 288.3236838862 degrees",
+    );
+    test_eval_simple(
+        "compass_bearing(0.9 radians, 0, 0.9 radians, 1)",
+        "approx. 89.6083327034 degrees",
+    );
+}
+
+#[test]
+fn decibel_power_ratios() {
+    test_eval_simple("dB_to_power_ratio(3)", "approx. 1.995262315");
+    test_eval_simple("dB_to_power_ratio(20)", "100");
+    test_eval_simple("power_ratio_to_dB(2)", "approx. 3.0102999566");
+    expect_error("power_ratio_to_dB(0)", Some("ratio must be positive"));
+}
+
+#[test]
+fn decibel_amplitude_ratios() {
+    test_eval_simple("dB_to_amplitude_ratio(40)", "100");
+    test_eval_simple("amplitude_ratio_to_dB(2)", "approx. 6.0205999132");
+    expect_error("amplitude_ratio_to_dB(-2)", Some("ratio must be positive"));
+}
+
+#[test]
+fn vswr_and_reflection_coefficient() {
+    test_eval_simple("VSWR_to_reflection(2)", "approx. 0.3333333333");
+    test_eval_simple("VSWR_to_reflection(1)", "0");
+    test_eval_simple("reflection_to_VSWR(1/3)", "2");
+    test_eval_simple("reflection_to_VSWR(-0.5)", "3");
+    expect_error("VSWR_to_reflection(0.5)", Some("VSWR must be at least 1"));
+    expect_error(
+        "reflection_to_VSWR(1)",
+        Some("reflection coefficient must be less than 1 in magnitude"),
+    );
+}
+
+#[test]
+fn ohms_law() {
+    test_eval("ohms_law(12 V, 2 A, ?)", "6 ohms");
+    test_eval("ohms_law(?, 2 A, 6 ohm)", "12 V");
+    test_eval("ohms_law(?, 2 A, 5 ohm)", "10 V");
+    test_eval("ohms_law(5 V, ?, 1000 ohm)", "0.005 A");
+    test_eval("ohms_law(0 V, ?, 1000 ohm)", "0 A");
+    expect_error("ohms_law(12 V, 2 kg, ?)", Some("units are incompatible"));
+    expect_error(
+        "ohms_law(12 V, 2 A, 6 ohm)",
+        Some("exactly one argument must be ?"),
+    );
+    expect_error(
+        "ohms_law(12 V, ?, ?)",
+        Some("exactly one argument must be ?"),
+    );
+}
+
+#[test]
+fn parallel() {
+    test_eval_simple("parallel(100 ohm, 200 ohm)", "approx. 66.6666666666 ohms");
+    test_eval("parallel(100 ohm, parallel(200 ohm, 200 ohm))", "50 ohms");
+    test_eval("reciprocal_sum(3, 6, 2)", "1");
+    expect_error("parallel(1 ohm, 1 kg)", Some("units are incompatible"));
+    expect_error(
+        "parallel(filter(x: 1, range(0, 1)))",
+        Some("expected at least 2 arguments"),
+    );
+}
+
+#[test]
+fn rc_time_constant() {
+    test_eval("rc_time_constant(10 kiloohm, 100 nanofarad) to ms", "1 ms");
+    test_eval("1 ohm * 1 farad to s", "1 s");
+    expect_error(
+        "rc_time_constant(1 kg, 1 farad)",
+        Some("units are incompatible"),
+    );
+}
+
+#[test]
+fn lc_resonance() {
+    test_eval_simple(
+        "lc_resonance(1 microhenry, 1 microfarad) to kHz",
+        "approx. 159.1549430918 kHz",
+    );
+    test_eval_simple(
+        "lc_resonance(1 millihenry, 1 microfarad)",
+        "approx. 5032.9212104877 Hz",
+    );
+}
+
+#[test]
+fn snells_law() {
+    test_eval_simple(
+        "snell(1.0, 45 degrees, 1.5)",
+        "approx. 28.125505702 degrees",
+    );
+    test_eval_simple("snell(1, 30°, 1)", "approx. 30°");
+    test_eval_simple("snell(1, 0.5, 1.5)", "approx. 0.3253252852");
+    expect_error(
+        "snell(1.5, 60°, 1)",
+        Some("total internal reflection: there is no refracted ray"),
+    );
+}
+
+#[test]
+fn gas_constant() {
+    test_eval("gas_constant", "8.31446261815324 J mol^-1 K^-1");
+    test_eval("1 atm to Pa", "101325 Pa");
+}
+
+#[test]
+fn ideal_gas_law() {
+    test_eval_simple(
+        "ideal_gas(1 atm, ?, 1 mol, 273.15 K)",
+        "approx. 22.413969545 L",
+    );
+    test_eval_simple(
+        "ideal_gas(1 atm, ?, 1 mol, 25 °C)",
+        "approx. 24.465403697 L",
+    );
+    test_eval_simple("ideal_gas(1 atm, ?, 1 mol, 0 °C)", "approx. 22.413969545 L");
+    test_eval_simple(
+        "ideal_gas(1 atm, 22.4 L, 1 mol, ?)",
+        "approx. 272.9797587933 K",
+    );
+    test_eval_simple(
+        "ideal_gas(?, 22.4 L, 1 mol, 273.15 K)",
+        "approx. 101388.1903637748 Pa",
+    );
+    test_eval_simple(
+        "ideal_gas(1 atm, 22.4 L, ?, 273.15 K)",
+        "approx. 0.9993767482 mol",
+    );
+    expect_error(
+        "ideal_gas(1 atm, ?, 1 kg, 300 K)",
+        Some("units are incompatible"),
+    );
+    expect_error(
+        "ideal_gas(1 atm, 1 L, 1 mol, 300 K)",
+        Some("exactly one argument must be ?"),
+    );
+    expect_error(
+        "ideal_gas(?, ?, 1 mol, 300 K)",
+        Some("exactly one argument must be ?"),
+    );
+}
+
+#[test]
+fn r_gas() {
+    test_eval("R_gas", "8.31446261815324 J mol^-1 K^-1");
+    test_eval("1 mol * R_gas * 300 K", "2494.338785445972 J");
+}
+
+#[test]
+fn electromagnetic_constants() {
+    test_eval("faraday", "96485.3321233100184 coulomb / mol");
+    test_eval_simple("mu_0 to H/m", "approx. 0.0000012566 H / m");
+    test_eval_simple(
+        "epsilon_0 to picofarad / m",
+        "approx. 8.8541878176 picofarad / m",
+    );
+    test_eval_simple("1 / sqrt(mu_0 epsilon_0) to m/s", "approx. 299792458 m / s");
+    test_eval_simple("epsilon_0", "approx. 8.8541878176 picofarad / m");
+    test_eval_simple("epsilon_0 to 3 sf", "approx. 8.85 picofarad / m");
+    test_eval_simple(
+        "help(epsilon_0)",
+        "epsilon_0: electric permittivity of vacuum",
+    );
+}
+
+#[test]
+fn stefan_boltzmann_constant() {
+    test_eval("stefan_boltzmann", "0.00000005670374419 W m^-2 K^-4");
+    test_eval(
+        "stefan_boltzmann * (300 K)^4 to W/m^2",
+        "459.300327939 W / m^2",
+    );
+}
+
+#[test]
+fn fine_structure_constant() {
+    test_eval_simple("fine_structure_constant", "approx. 0.0072973525");
+    test_eval_simple("1/alpha", "approx. 137.0359990836");
+}
+
+#[test]
+fn dimensionless_constants() {
+    test_eval_simple("euler_mascheroni", "approx. 0.5772156649");
+    test_eval_simple("apery_constant", "approx. 1.2020569031");
+    test_eval_simple("catalan_constant", "approx. 0.9159655941");
+}
+
+#[test]
+fn ordinal_words() {
+    test_eval_simple("ordinal_words(3)", "third");
+    test_eval_simple("ordinal_words(21)", "twenty-first");
+    test_eval_simple("ordinal_words(12)", "twelfth");
+    test_eval_simple("ordinal_words(40)", "fortieth");
+    test_eval_simple("ordinal_words(0)", "zeroth");
+    test_eval_simple("ordinal_words(123)", "one hundred twenty-third");
+    test_eval_simple("ordinal_words(1000000)", "one millionth");
+    expect_error("ordinal_words(-1)", None);
+}
+
+#[test]
+fn greek_letter_constants() {
+    test_eval_simple("alpha", "approx. 0.0072973525");
+    test_eval_simple("gamma", "approx. 0.5772156649");
+    test_eval_simple("sigma", "0.00000005670374419 W m^-2 K^-4");
+    test_eval_simple("mu", "approx. 0.0000012566 N / A^2");
+    test_eval_simple("epsilon", "approx. 8.8541878176 picofarad / m");
+    expect_error("phi", Some("unknown identifier 'phi'"));
+}
+
+#[test]
+fn greek_letter_symbols_are_not_constants() {
+    expect_error("μ", Some("unknown identifier 'μ'"));
+    expect_error("γ", Some("unknown identifier 'γ'"));
+    test_eval_simple("μs to ns", "1000 ns");
+    test_eval_simple("5 μm to nm", "5000 nm");
+}
+
+#[test]
+fn greek_letters_as_parameters() {
+    test_eval("(gamma: gamma * 2) 3", "6");
+    test_eval("(omega: omega * 2) 3", "6");
+    test_eval("(lambda: lambda + 1) 3", "4");
+}
+
+#[test]
+fn fractions_as_words() {
+    test_eval_simple("(3/4) as words", "three quarters");
+    test_eval_simple("(1/3) as words", "one third");
+    test_eval_simple("(2/5) as words", "two fifths");
+    test_eval_simple("(1/2) as words", "one half");
+    test_eval_simple("1.5 as words", "three halves");
+    test_eval_simple("(7/25) as words", "seven twenty-fifths");
+    test_eval_simple("-0.25 as words", "minus one quarter");
+}
+
+#[test]
+fn integers_as_words() {
+    test_eval_simple("42 as words", "forty-two");
+    test_eval_simple("0 as words", "zero");
+    test_eval_simple("1000001 as words", "one million one");
+    expect_error(
+        "sqrt 2 as words",
+        Some("unable to write this number as words"),
+    );
+    expect_error(
+        "2 kg as words",
+        Some("only unitless numbers can be written as words"),
+    );
+}
+
+#[test]
+fn durations_as_clock_times() {
+    test_eval_simple("3661 seconds as clock", "1:01:01");
+    test_eval_simple("13.5 hours as clock_24h", "13:30:00");
+    test_eval_simple("25 hours as clock", "1:00:00");
+    test_eval_simple("3661 seconds as clock_12h", "01:01 AM");
+    test_eval_simple("13.5 hours as clock_12h", "01:30 PM");
+    test_eval_simple("0 seconds as clock_12h", "12:00 AM");
+    test_eval_simple("12 hours as clock_12h", "12:00 PM");
+    expect_error(
+        "-1 s as clock",
+        Some("only non-negative durations can be formatted as a clock time"),
+    );
+    expect_error(
+        "1 kg as clock",
+        Some("only durations can be formatted as a clock time"),
+    );
+}
+
+#[test]
+fn timeout_remaining() {
+    use fend_core::Interrupt;
+    use std::time::Duration;
+
+    let timeout = fend_core::Timeout::new(Duration::from_secs(5));
+    let remaining = timeout.remaining().unwrap();
+    assert!(remaining > Duration::from_secs(4) && remaining <= Duration::from_secs(5));
+    assert!(!timeout.should_interrupt());
+
+    let expired = fend_core::Timeout::new(Duration::from_millis(0));
+    std::thread::sleep(Duration::from_millis(1));
+    assert_eq!(expired.remaining(), None);
+    assert!(expired.should_interrupt());
+}
+
+#[test]
+fn context_with_timeout() {
+    use std::time::Duration;
+
+    let mut context = Context::new();
+    let result = context
+        .with_timeout(Duration::from_secs(60))
+        .evaluate("1+1")
+        .unwrap();
+    assert_eq!(result.get_main_result(), "2");
+
+    let mut with_timeout = context.with_timeout(Duration::from_millis(0));
+    std::thread::sleep(Duration::from_millis(1));
+    assert_eq!(with_timeout.evaluate("100000!").unwrap_err(), "interrupted");
+}
+
+#[test]
+fn max_iterations_interrupt() {
+    let mut context = Context::new();
+    let int = fend_core::MaxIterationsInterrupt::new(1_000_000);
+    let result = fend_core::evaluate_with_interrupt("1+1", &mut context, &int).unwrap();
+    assert_eq!(result.get_main_result(), "2");
+    assert!(int.remaining() < 1_000_000);
+
+    let int = fend_core::MaxIterationsInterrupt::new(10);
+    assert_eq!(
+        fend_core::evaluate_with_interrupt("100000!", &mut context, &int).unwrap_err(),
+        "interrupted"
+    );
+    assert_eq!(int.remaining(), 0);
+}
+
+#[track_caller]
+fn test_markdown(input: &str, expected: &str) {
+    let mut context = Context::new();
+    let result = evaluate(input, &mut context).unwrap();
+    assert_eq!(result.to_markdown(), expected);
+}
+
+#[test]
+fn markdown_output() {
+    test_markdown("pi", "\u{2248} **3.1415926535**");
+    test_markdown("1/3 as fraction", "**1/3**");
+    test_markdown("3 m^2", "**3** m\u{b2}");
+    test_markdown("1 kg m^2 / s", "**1** kg m\u{b2} / s");
+    test_markdown("1 / s", "**1** s\u{207b}\u{b9}");
+    test_markdown("1 + 2i", "**1 + 2i**");
+    test_markdown("\"a_b*\"", r"a\_b\*");
+    test_markdown("", "");
+}
+
+#[test]
+fn operator_spans() {
+    let mut context = Context::new();
+    let result = evaluate("9.8 ± 0.1", &mut context).unwrap();
+    let operators = result
+        .get_main_result_spans()
+        .filter(|span| span.kind() == fend_core::SpanKind::Operator)
+        .map(fend_core::SpanRef::string)
+        .collect::<Vec<_>>();
+    assert_eq!(operators, vec![" ± "]);
+    test_latex("9.8 ± 0.1", Some(r"9.8 \pm 0.1"));
+}
+
+#[test]
+fn partial_evaluation() {
+    let mut context = Context::new();
+    context.set_partial_evaluation(true);
+    let result = evaluate("1 + unknown_unit + 2", &mut context).unwrap();
+    assert_eq!(result.get_main_result(), "3 + unknown_unit");
+    let errors = result
+        .get_main_result_spans()
+        .filter(|span| span.kind() == fend_core::SpanKind::Error)
+        .map(fend_core::SpanRef::string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors, vec!["unknown_unit"]);
+    assert_eq!(
+        evaluate("5 - foo - xyzzy", &mut context)
+            .unwrap()
+            .get_main_result(),
+        "5 - foo - xyzzy"
+    );
+    assert_eq!(
+        evaluate("1 + 2", &mut context).unwrap().get_main_result(),
+        "3"
+    );
+    assert_eq!(
+        evaluate("foo + xyzzy", &mut context).unwrap_err(),
+        "unknown identifier 'foo'"
+    );
+    assert_eq!(
+        evaluate("1 kg + 1 m", &mut context).unwrap_err(),
+        "units are incompatible"
+    );
+
+    context.set_partial_evaluation(false);
+    assert_eq!(
+        evaluate("1 + unknown_unit", &mut context).unwrap_err(),
+        "unknown identifier 'unknown_unit'"
+    );
+}
+
+#[test]
+fn no_overflow_at_u64_boundary() {
+    test_eval("18446744073709551615 + 1", "18446744073709551616");
+    test_eval("4294967296 * 4294967296", "18446744073709551616");
+    test_eval("0 - 18446744073709551615 - 1", "-18446744073709551616");
+    test_eval("2^64 * 2^64", "340282366920938463463374607431768211456");
+    test_eval("18446744073709551616 - 1", "18446744073709551615");
+}
+
+#[test]
+fn repeated_unit_queries_use_cached_definitions() {
+    let mut context = Context::new();
+    for _ in 0..3 {
+        let result = evaluate("1 kWh to MJ", &mut context).unwrap();
+        assert_eq!(result.get_main_result(), "3.6 MJ");
+        let result = evaluate("5 kilometres to miles", &mut context).unwrap();
+        assert_eq!(result.get_main_result(), "approx. 3.1068559611 miles");
+    }
+}
+
+#[test]
+fn boolean_operators() {
+    test_eval("1 < 2 and 2 < 3", "1");
+    test_eval("1 < 2 and 3 < 2", "0");
+    test_eval("1 > 2 or 3 > 2", "1");
+    test_eval("0 or 0", "0");
+    test_eval("2 and 3", "1");
+    test_eval("1 and 0 or 1", "1");
+    test_eval("1 or 1 and 0", "1");
+    test_eval("(x: x > 0 and x < 10) 5", "1");
+    expect_error("\"a\" and 1", Some("expected a number"));
+}
+
+#[test]
+fn boolean_operators_short_circuit() {
+    test_eval("0 and (1/0)", "0");
+    test_eval("1 or (1/0)", "1");
+    test_eval("0 and unknown_identifier", "0");
+    expect_error("1 and (1/0)", Some("division by zero"));
+}
+
+#[test]
+fn memoised_functions() {
+    test_eval("memo(x: x^2) 5", "25");
+    test_eval("map(memo(x: x^2), (1, 2, 3))", "(1, 4, 9)");
+    test_eval("memo(sqrt) 16", "4");
+    test_eval("memo(x: y: x + y)(1, 2)", "3");
+    test_eval("memo(memo(sqrt)) 4", "2");
+    test_eval_simple(
+        "map(memo(x: x), (16, 16 to hex, 1/2, 1/2 to frac))",
+        "(16, 10, 0.5, 1/2)",
+    );
+    test_eval_simple(
+        "map(memo(x: x), (pi, pi to 2 dp))",
+        "(approx. 3.1415926535, approx. 3.14)",
+    );
+    expect_error("memo 5", Some("memo expects a function"));
+}
+
+#[test]
+fn memoised_functions_reuse_results() {
+    fn steps(input: &str) -> u64 {
+        let mut context = Context::new();
+        let int = fend_core::MaxIterationsInterrupt::new(1_000_000);
+        let result = fend_core::evaluate_with_interrupt(input, &mut context, &int).unwrap();
+        assert_eq!(result.get_main_result(), "(21, 21, 21, 21)");
+        1_000_000 - int.remaining()
+    }
+    let f = "(x: (x + x + x + x + x + x) / 2)";
+    let plain = steps(&format!("map({}, (7, 7, 7, 7))", f));
+    let memoised = steps(&format!("map(memo {}, (7, 7, 7, 7))", f));
+    assert!(memoised < plain);
+    let equal_values = steps(&format!("map(memo {}, (7, 14 / 2, 7.0, 3.5 * 2))", f));
+    assert!(equal_values < plain);
+}
+
+#[test]
+fn memoised_function_shares_cache_between_call_sites() {
+    fn steps(input: &str, expected: &str) -> u64 {
+        let mut context = Context::new();
+        let int = fend_core::MaxIterationsInterrupt::new(1_000_000);
+        let result = fend_core::evaluate_with_interrupt(input, &mut context, &int).unwrap();
+        assert_eq!(result.get_main_result(), expected);
+        1_000_000 - int.remaining()
+    }
+    let f = format!("(x: 3 * x{})", " + x - x".repeat(20));
+    let one_call = steps(&format!("(g: g 7)(memo {})", f), "21");
+    let plain = steps(&format!("(g: (g 7, g 7, g 7))({})", f), "(21, 21, 21)");
+    let memoised = steps(&format!("(g: (g 7, g 7, g 7))(memo {})", f), "(21, 21, 21)");
+    // the second and third call are answered from the cache of the first,
+    // so they cost much less than evaluating the function again
+    assert!(memoised - one_call < (plain - one_call) / 4);
+}
+
+#[test]
+fn tail_calls_do_not_overflow_the_stack() {
+    let mut context = Context::new();
+    let int = fend_core::MaxIterationsInterrupt::new(20_000);
+    assert_eq!(
+        fend_core::evaluate_with_interrupt("(x: x x) (x: x x)", &mut context, &int).unwrap_err(),
+        "interrupted"
+    );
+}
+
+#[test]
+fn curried_function_calls() {
+    test_eval("(x: y: x + y)(1, 2)", "3");
+    test_eval("(f: f(1, 2) + 1)(a: b: a * 10 + b)", "13");
+    test_eval("(f: f(1, 2))(a: b: c: a + b + c) 3", "6");
+    test_eval("(x: x)(y: y) 5", "5");
+}
+
+#[test]
+fn engineering_notation_with_si_prefixes() {
+    test_eval_simple("0.001234 as engineering_prefixed", "1.234 m");
+    test_eval_simple("1234000 as engineering_prefixed", "1.234 M");
+    test_eval_simple("1234 as engineering_prefixed", "1.234 k");
+    test_eval("5 as engineering_prefixed", "5");
+    test_eval("1234 m as engineering_prefixed", "1.234 km");
+    test_eval("1234 km as engineering_prefixed", "1.234 Mm");
+    test_eval("1234 cm as engineering_prefixed", "12.34 m");
+    test_eval("-0.00002 s as engineering_prefixed", "-20 \u{b5}s");
+    test_eval("1234 metres as engineering_prefixed", "1.234 kilometres");
+    expect_error(
+        "5 m^2 as engineering_prefixed",
+        Some("SI prefixes can only be used with a single unit, e.g. `m` or `g`"),
+    );
+    expect_error(
+        "i as engineering_prefixed",
+        Some("cannot use SI prefixes for a complex number"),
+    );
+}
+
+#[test]
+fn integer_and_fractional_parts() {
+    test_eval("integer_part(3.7)", "3");
+    test_eval("fractional_part(3.7)", "0.7");
+    test_eval("integer_part(-3.7)", "-3");
+    test_eval("fractional_part(-3.7)", "-0.7");
+    test_eval("integer_part(3.7 kg)", "3 kg");
+    test_eval("fractional_part(3.7 kg)", "0.7 kg");
+    test_eval("integer_part 5", "5");
+    test_eval("fractional_part 5", "0");
+    expect_error(
+        "integer_part i",
+        Some("only real numbers can be split into integer and fractional parts"),
+    );
+}
+
+#[test]
+fn balanced_ternary() {
+    test_eval_simple("to_balanced_ternary(5)", "1TT");
+    test_eval_simple("to_balanced_ternary 0", "0");
+    test_eval_simple("7 as balanced_ternary", "1T1");
+    test_eval_simple("-5 as balanced_ternary", "T11");
+    test_eval_simple("to_balanced_ternary(-1)", "T");
+}
+
+#[test]
+fn leap_years() {
+    test_eval("is_leap_year(2024)", "1");
+    test_eval("is_leap_year(2023)", "0");
+    test_eval("is_leap_year(1900)", "0");
+    test_eval("is_leap_year(2000)", "1");
+    test_eval("days_in_year(2024)", "366");
+    test_eval("days_in_year(1900)", "365");
+    expect_error("is_leap_year(0)", Some("year 0 does not exist"));
+    expect_error("days_in_year(2024.5)", None);
+}
+
+#[test]
+fn easter_dates() {
+    test_eval_simple("easter(2024)", "Sunday, 31 March 2024");
+    test_eval_simple("easter_western(2000)", "Sunday, 23 April 2000");
+    test_eval_simple("easter_orthodox(2024)", "Sunday, 5 May 2024");
+    test_eval_simple("easter_orthodox(2025)", "Sunday, 20 April 2025");
+    expect_error(
+        "easter(1500)",
+        Some("Easter can only be calculated for years from 1583 onwards"),
+    );
+}
+
+#[test]
+fn convert_currency_with_explicit_rate() {
+    test_eval("convert_currency(100 USD, EUR, 0.92)", "92 EUR");
+    test_eval("convert_currency(20 GBP, USD, 1.25)", "25 USD");
+    expect_error(
+        "convert_currency(100, EUR, 0.92)",
+        Some("expected an amount of money and a currency"),
+    );
+    expect_error(
+        "convert_currency(100 USD, kg, 0.92)",
+        Some("expected an amount of money and a currency"),
+    );
+    expect_error(
+        "convert_currency(100 USD, EUR, 0.92 kg)",
+        Some("exchange rate must not have a unit"),
+    );
+}
+
+#[test]
+fn exchange_rate_provider() {
+    struct FixedRates;
+
+    impl fend_core::ExchangeRateProvider for FixedRates {
+        fn get_rate(&self, from: &str, to: &str) -> Option<f64> {
+            assert_eq!(to, "EUR");
+            match from {
+                "USD" => Some(0.92),
+                "GBP" => Some(1.15),
+                _ => None,
+            }
+        }
+    }
+
+    let mut context = Context::new();
+    context.set_exchange_rate_provider(FixedRates);
+    let mut eval = |input| {
+        evaluate(input, &mut context)
+            .unwrap()
+            .get_main_result()
+            .to_string()
+    };
+    assert_eq!(eval("100 USD to EUR"), "92 EUR");
+    assert_eq!(eval("100 dollars to EUR"), "92 EUR");
+    assert_eq!(eval("23 GBP to USD"), "28.75 USD");
+    // currencies without a rate from the provider use the built-in rates
+    assert_eq!(eval("1 EUR to CHF"), "approx. 0.9063717937 CHF");
+}
+
+#[test]
+fn exchange_rate_provider_can_be_replaced() {
+    struct Rate(f64);
+
+    impl fend_core::ExchangeRateProvider for Rate {
+        fn get_rate(&self, from: &str, _to: &str) -> Option<f64> {
+            if from == "USD" {
+                Some(self.0)
+            } else {
+                None
+            }
+        }
+    }
+
+    let mut context = Context::new();
+    let eval = |context: &mut Context, input| {
+        evaluate(input, context)
+            .unwrap()
+            .get_main_result()
+            .to_string()
+    };
+    context.set_exchange_rate_provider(Rate(0.5));
+    assert_eq!(eval(&mut context, "10 USD to EUR"), "5 EUR");
+    assert_eq!(eval(&mut context, "10 dollars to EUR"), "5 EUR");
+    context.set_exchange_rate_provider(Rate(0.25));
+    assert_eq!(eval(&mut context, "10 USD to EUR"), "2.5 EUR");
+    assert_eq!(eval(&mut context, "1000 cents to EUR"), "2.5 EUR");
+    assert_eq!(eval(&mut context, "1 m to cm"), "100 cm");
+}
+
+#[test]
+fn context_is_send_and_sync() {
+    fn assert_send_and_sync<T: Send + Sync>() {}
+    assert_send_and_sync::<Context>();
+}
+
+#[test]
+fn always_ratio_format() {
+    test_eval_simple("5 as ratio", "5/1");
+    test_eval_simple("-3 as ratio", "-3/1");
+    test_eval_simple("0 as ratio", "0/1");
+    test_eval_simple("(1/3 + 1/6) as ratio", "1/2");
+    test_eval_simple("0.75 as ratio", "3/4");
+    test_eval_simple("5 kg as ratio", "5/1 kg");
+    test_eval("4 as fraction", "4");
+}
+
+#[test]
+fn exponent_and_mantissa() {
+    test_eval("exponent(1234, 10)", "3");
+    test_eval("mantissa(1234, 10)", "1.234");
+    test_eval("exponent(8, 2)", "3");
+    test_eval("mantissa(8, 2)", "1");
+    test_eval("mantissa(12, 2)", "1.5");
+    test_eval("exponent(0.00123, 10)", "-3");
+    test_eval("mantissa(-0.00123, 10)", "-1.23");
+    expect_error("exponent(0, 10)", Some("zero has no exponent or mantissa"));
+    expect_error("mantissa(5, 1)", Some("base must be greater than 1"));
+}
+
+#[test]
+fn dump_scope() {
+    test_eval_simple("dump_scope", "{\n}");
+    test_eval_simple("(x: dump_scope) 5", "{\n    x: 5\n}");
+    test_eval_simple("(x: y: dump_scope) 1 2", "{\n    y: 2,\n    x: 1\n}");
+    test_eval_simple("(x: (x: dump_scope) 3) 5", "{\n    x: 3\n}");
+    test_eval_simple("(f: dump_scope) (x: x + 1)", "{\n    f: \\x.(x+1)\n}");
+}
+
+#[test]
+fn apply_at_iterates_a_function() {
+    test_eval("apply_at(x: x*2, 3, 1)", "8");
+    test_eval("apply_at((x: x + 1), 0, 5)", "5");
+    test_eval("apply_at(sqrt, 2, 16)", "2");
+    expect_error("apply_at((x: x), 1)", Some("expected 3 arguments"));
+}
+
+#[test]
+fn apply_at_can_be_interrupted() {
+    let mut context = Context::new();
+    let int = fend_core::MaxIterationsInterrupt::new(1000);
+    assert_eq!(
+        fend_core::evaluate_with_interrupt("apply_at((x: x), 10^9, 1)", &mut context, &int)
+            .unwrap_err(),
+        "interrupted"
+    );
+}
+
+#[test]
+fn compose_functions() {
+    test_eval("compose(sqrt, (x: x * x))(3)", "3");
+    test_eval("compose(sin, sqrt)(4)", "approx. 0.9092974268");
+    test_eval("(sqrt \u{2218} (x: x + 7)) 9", "4");
+    test_eval("(sqrt \u{2218} sqrt \u{2218} abs)(-16)", "2");
+    expect_error("compose(2, sin)", Some("only functions can be composed"));
+    expect_error("sin \u{2218} 2", Some("only functions can be composed"));
+}
+
+#[test]
+fn continued_fraction_convergents() {
+    test_eval_simple("convergents(pi, 4)", "(3/1, 22/7, 333/106, 355/113)");
+    test_eval_simple("convergents(sqrt 2, 5)", "(1/1, 3/2, 7/5, 17/12, 41/29)");
+    test_eval_simple("convergents(7/3, 10)", "(2/1, 7/3)");
+    test_eval_simple("convergents(-7/3, 10)", "(-2/1, -7/3)");
+    test_eval_simple("convergents(0.5, 0)", "()");
+    expect_error(
+        "convergents(1 kg, 2)",
+        Some("convergents are only supported for real unitless numbers"),
+    );
+}
+
+#[test]
+fn bernstein_polynomials() {
+    test_eval("bernstein(2, 1, 0.5)", "0.5");
+    test_eval("bernstein(4, 2, 0.25)", "0.2109375");
+    test_eval("bernstein(3, 0, 0)", "1");
+    test_eval("bernstein(3, 3, 1)", "1");
+    test_eval_simple("bernstein(3, 1, 1/3) to fraction", "4/9");
+    expect_error("bernstein(3, 4, 0.5)", Some("k must not be greater than n"));
+    expect_error("bernstein(3, 1, 1.5)", Some("t must be between 0 and 1"));
+    expect_error("bernstein(3, 1, -0.1)", Some("t must be between 0 and 1"));
+    expect_error("bernstein(3, 1)", Some("expected 3 arguments"));
+}